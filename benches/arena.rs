@@ -0,0 +1,89 @@
+//! What building the syntax tree and the arena tree costs for the same source: the time, the
+//! number of allocations, and the memory the built tree holds.
+//!
+//! Run with `cargo bench --bench arena`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use from_pest::FromPest;
+use pest::Parser;
+use leekwars_parser::arena::Ast;
+use leekwars_parser::ast::lsv4root::Lsv4Root;
+use leekwars_parser::lsv4::{Lsv4Parser, Rule};
+
+const ROWS: usize = 4_000;
+
+/// The system allocator, counting the allocations and the bytes in use
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        LIVE.fetch_add(new_size, Ordering::Relaxed);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// A generated fight script, one function per row
+fn source(rows: usize) -> String {
+    (0..rows)
+        .map(|row| {
+            format!(
+                "function attack{row}(leek, weapon) {{\n    var cell = getCell(leek) + {row}; // target\n    \
+                 if (canUseWeapon(weapon, leek) && cell != null) {{ useWeapon(leek); }}\n    \
+                 return [cell, 'row {row}', weapon];\n}}\n"
+            )
+        })
+        .collect()
+}
+
+/// The fastest build out of several runs, with the allocations it makes and the bytes the tree holds
+fn measure<T>(build: impl Fn() -> T) -> (Duration, usize, usize) {
+    let mut fastest = Duration::MAX;
+    let (mut allocations, mut bytes) = (0, 0);
+    for _ in 0..5 {
+        let (before, live) = (ALLOCATIONS.load(Ordering::Relaxed), LIVE.load(Ordering::Relaxed));
+        let start = Instant::now();
+        let tree = build();
+        fastest = fastest.min(start.elapsed());
+        allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        bytes = LIVE.load(Ordering::Relaxed) - live;
+        drop(tree);
+    }
+    (fastest, allocations, bytes)
+}
+
+fn main() {
+    let source = source(ROWS);
+    let pairs = Lsv4Parser::parse(Rule::lsv4_root, &source).expect("the generated source parses");
+    let syntax = measure(|| Lsv4Root::from_pest(&mut pairs.clone()).expect("the generated source converts"));
+    let arena = measure(|| Ast::from_pairs(&source, pairs.clone()).expect("the generated source builds"));
+    for (name, (time, allocations, bytes)) in [("syntax tree", syntax), ("arena", arena)] {
+        println!("{name:>11}: {time:>9.2?} {allocations:>9} allocations {:>9} KiB", bytes / 1024);
+    }
+    println!(
+        "      ratio: x{:.1} faster, x{:.0} fewer allocations, x{:.1} less memory",
+        syntax.0.as_secs_f64() / arena.0.as_secs_f64(),
+        syntax.1 as f64 / arena.1.max(1) as f64,
+        syntax.2 as f64 / arena.2.max(1) as f64,
+    )
+}
@@ -0,0 +1,45 @@
+//! How long building the syntax tree takes, for growing numbers of statements and of elements in a
+//! single literal. The time per item must stay the same as they grow: the conversion is linear.
+//!
+//! Run with `cargo bench --bench conversion`.
+
+use std::time::{Duration, Instant};
+use from_pest::FromPest;
+use pest::Parser;
+use leekwars_parser::ast::lsv4root::Lsv4Root;
+use leekwars_parser::lsv4::{Lsv4Parser, Rule};
+
+const SIZES: [usize; 4] = [2_000, 4_000, 8_000, 16_000];
+
+/// A generated data table, one statement per row
+fn statements(rows: usize) -> String {
+    (0..rows).map(|row| format!("var row{row} = [{row}, 'name {row}', {row}.5, true]; // row {row}\n")).collect()
+}
+
+/// A single array literal with all the rows in it
+fn elements(rows: usize) -> String {
+    let rows: Vec<_> = (0..rows).map(|row| format!("  [{row}, 'name {row}', {row}.5]")).collect();
+    format!("global TABLE = [\n{}\n];\n", rows.join(",\n"))
+}
+
+/// The fastest conversion of `source` out of several runs
+fn convert(source: &str) -> Duration {
+    let pairs = Lsv4Parser::parse(Rule::lsv4_root, source).expect("the generated source parses");
+    (0..5).map(|_| {
+        let start = Instant::now();
+        Lsv4Root::from_pest(&mut pairs.clone()).expect("the generated source converts");
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn main() {
+    for (name, generate) in [("statements", statements as fn(usize) -> String), ("elements", elements)] {
+        let mut first = None;
+        for rows in SIZES {
+            let time = convert(&generate(rows));
+            let per_row = time.as_secs_f64() * 1e9 / rows as f64;
+            let ratio = per_row / *first.get_or_insert(per_row);
+            println!("{name:>10} {rows:>6}: {:>9.2?} {per_row:>8.0} ns/row  x{ratio:.2}", time);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use crate::lexer::{RESERVED, VERSIONED_KEYWORDS};
+
+/// An interned identifier or keyword, two occurrences of the same name have the same symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(NonZeroU32);
+
+impl Symbol {
+    fn index(self) -> usize {
+        self.0.get() as usize - 1
+    }
+}
+
+/// The names of a source, borrowed from it.
+///
+/// The keywords are interned first, in the same order for every source, so their symbols can be
+/// compared across trees.
+#[derive(Debug, Clone)]
+pub struct Interner<'src> {
+    symbols: HashMap<&'src str, Symbol>,
+    names: Vec<&'src str>,
+}
+
+impl<'src> Interner<'src> {
+    pub fn new() -> Self {
+        let mut interner = Interner { symbols: HashMap::new(), names: Vec::new() };
+        for keyword in RESERVED.into_iter().chain(VERSIONED_KEYWORDS).chain(["constructor", "include"]) {
+            interner.intern(keyword);
+        }
+        interner
+    }
+
+    pub fn intern(&mut self, name: &'src str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return *symbol;
+        }
+        let symbol = Symbol(NonZeroU32::new(self.names.len() as u32 + 1).expect("fewer than 2^32 names"));
+        self.symbols.insert(name, symbol);
+        self.names.push(name);
+        symbol
+    }
+
+    /// The symbol of `name`, `None` if it is neither a keyword nor a name of the source
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// The name `symbol` stands for. The symbols of another interner, keywords excepted, stand for
+    /// another name or make it panic: [`Interner::name`] returns `None` for them instead.
+    pub fn resolve(&self, symbol: Symbol) -> &'src str {
+        self.names[symbol.index()]
+    }
+
+    /// The name `symbol` stands for, `None` if it is past the names of this interner
+    pub fn name(&self, symbol: Symbol) -> Option<&'src str> {
+        self.names.get(symbol.index()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl Default for Interner<'_> {
+    fn default() -> Self {
+        Interner::new()
+    }
+}
@@ -0,0 +1,416 @@
+mod interner;
+mod nodes;
+
+pub use interner::{Interner, Symbol};
+pub use nodes::*;
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use pest::iterators::{Pair, Pairs};
+use crate::ast::Span;
+use crate::lsv4::Rule;
+use crate::diagnostic::{codes, Diagnostic};
+use crate::{check_depth, grow_stack, validated_pairs, ParseErrors, ParseOptions};
+
+/// A syntax tree stored in a single vector, borrowing its text from the source.
+///
+/// The nodes are the pairs of the grammar, in the order of the source, without the whitespace and
+/// without the rules that only wrap a single node: `1` is a `Number` right under the node that
+/// contains it, not 20 levels of expressions down. Identifiers and keywords are interned. The
+/// nodes are read through [`Node`], or through the typed nodes such as [`Statement`] or
+/// [`Expression`]. Positions and nodes are counted with `u32`, the source can be at most
+/// `u32::MAX` bytes long.
+#[derive(Debug, Clone)]
+pub struct Ast<'src> {
+    source: &'src str,
+    nodes: Vec<NodeData>,
+    interner: Interner<'src>,
+}
+
+/// Where a node is in its [`Ast`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+#[derive(Debug, Clone)]
+struct NodeData {
+    rule: Rule,
+    /// Set on identifiers and keywords
+    symbol: Option<Symbol>,
+    /// Byte range without the comments the node ends with
+    start: u32,
+    end: u32,
+    /// The nodes after this one up to this index are its descendants
+    subtree_end: u32,
+}
+
+/// Parses a LeekScript source into an [`Ast`], rejecting it when [`crate::parse`] reports
+/// syntax errors or validation problems
+pub fn parse(source: &str, options: ParseOptions) -> Result<Ast<'_>, ParseErrors> {
+    let pairs = validated_pairs(source, options)?;
+    Ast::from_pairs(source, pairs)
+}
+
+impl<'src> Ast<'src> {
+    /// The tree of the pairs `Lsv4Parser` gives for `lsv4_root`, rejecting other pairs, pairs
+    /// nested too deeply and sources too long for the tree
+    pub fn from_pairs(source: &'src str, pairs: Pairs<'src, Rule>) -> Result<Self, ParseErrors> {
+        let error = |diagnostic| Err(ParseErrors { diagnostics: vec![diagnostic] });
+        let mut roots = pairs.clone();
+        match (roots.next(), roots.next()) {
+            (Some(root), None) if root.as_rule() == Rule::lsv4_root => {}
+            (pair, _) => {
+                let (start, end) = pair.map_or((0, 0), |pair| (pair.as_span().start(), pair.as_span().end()));
+                return error(Diagnostic::error(codes::CONVERSION_FAILURE, "Could not build the arena tree: expected the pair of `lsv4_root`", start, end));
+            }
+        }
+        if let Some(diagnostic) = check_depth(&pairs, 0) {
+            return error(diagnostic);
+        }
+        if u32::try_from(source.len()).is_err() {
+            return error(too_long(source));
+        }
+
+        let mut builder = Builder {
+            nodes: Vec::with_capacity(source.len() / 4),
+            interner: Interner::new(),
+        };
+        for pair in pairs {
+            builder.pair(pair);
+        }
+        // The spans fit in a `u32` with the source, the number of nodes is only known now
+        if u32::try_from(builder.nodes.len()).is_err() {
+            return error(too_long(source));
+        }
+        Ok(Ast { source, nodes: builder.nodes, interner: builder.interner })
+    }
+
+    pub fn source(&self) -> &'src str {
+        self.source
+    }
+
+    pub fn root(&self) -> Root<'_> {
+        Root::cast(self.node(NodeId(0))).expect("the tree of `lsv4_root`")
+    }
+
+    /// The node at `id`, which panics if the id comes from a bigger tree: [`Ast::get`] returns
+    /// `None` instead
+    pub fn node(&self, id: NodeId) -> Node<'_> {
+        self.get(id).unwrap_or_else(|| panic!("no node {id:?} in the tree"))
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<Node<'_>> {
+        ((id.0 as usize) < self.nodes.len()).then_some(Node { ast: self, id })
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn interner(&self) -> &Interner<'src> {
+        &self.interner
+    }
+
+    /// The name `symbol` stands for, see [`Interner::resolve`] for the symbols of another tree
+    pub fn resolve(&self, symbol: Symbol) -> &'src str {
+        self.interner.resolve(symbol)
+    }
+}
+
+fn too_long(source: &str) -> Diagnostic {
+    Diagnostic::error(codes::TOO_LONG, "The source is too long for an arena tree", 0, source.len())
+        .with_note(format!("An arena tree holds at most {} bytes and as many nodes", u32::MAX))
+}
+
+struct Builder<'src> {
+    nodes: Vec<NodeData>,
+    interner: Interner<'src>,
+}
+
+impl<'src> Builder<'src> {
+    /// Adds the nodes of `pair`, returning where its content ends
+    fn pair(&mut self, pair: Pair<'src, Rule>) -> usize {
+        let rule = pair.as_rule();
+        let span = pair.as_span();
+        let inner = pair.into_inner();
+        if wraps_single_node(rule, &inner) {
+            return self.children(inner, span.end());
+        }
+
+        // `Ast::from_pairs` checks that the positions and the number of nodes fit in a `u32`
+        let id = self.nodes.len();
+        let symbol = is_name(rule).then(|| self.interner.intern(span.as_str()));
+        self.nodes.push(NodeData {
+            rule,
+            symbol,
+            start: span.start() as u32,
+            end: span.end() as u32,
+            subtree_end: 0,
+        });
+        // The text of a comment is all there is to it
+        let end = match rule {
+            Rule::COMMENT => span.end(),
+            _ => self.children(inner, span.end()),
+        };
+        self.nodes[id].end = end as u32;
+        self.nodes[id].subtree_end = self.nodes.len() as u32;
+        end
+    }
+
+    /// Adds the nodes of the inner pairs of a pair ending at `end`, returning where the content
+    /// of that pair ends
+    fn children(&mut self, pairs: Pairs<'src, Rule>, mut end: usize) -> usize {
+        let mut last_content_end = end;
+        for pair in pairs.clone() {
+            match pair.as_rule() {
+                Rule::WHITESPACE => {}
+                Rule::COMMENT => {
+                    self.pair(pair);
+                }
+                _ => last_content_end = grow_stack(|| self.pair(pair)),
+            }
+        }
+        // As `content_end` does for the syntax tree
+        for pair in pairs.rev() {
+            // Something that isn't a pair, like a string in the grammar, comes after
+            if pair.as_span().end() != end {
+                break;
+            }
+            match pair.as_rule() {
+                Rule::COMMENT | Rule::WHITESPACE => end = pair.as_span().start(),
+                _ => return last_content_end,
+            }
+        }
+        end
+    }
+}
+
+/// The rules that are left out of the tree when they only hold one node. A `;` alone keeps its
+/// statement, telling an empty statement from the `;` ending another one.
+fn wraps_single_node(rule: Rule, inner: &Pairs<Rule>) -> bool {
+    let wrapper = matches!(
+        rule,
+        Rule::Statement
+            | Rule::ClassStatement
+            | Rule::InterfaceMember
+            | Rule::ForInnerCond
+            | Rule::ForInit
+            | Rule::SwitchClause
+            | Rule::Expression
+            | Rule::LambdaBody
+            | Rule::TernaryExpression
+            | Rule::LogicalORExpression
+            | Rule::LogicalXORExpression
+            | Rule::LogicalANDExpression
+            | Rule::EqualityExpression
+            | Rule::RelationalExpression
+            | Rule::BooleanORExpression
+            | Rule::BooleanXORExpression
+            | Rule::BooleanANDExpression
+            | Rule::ShiftExpression
+            | Rule::AdditiveExpression
+            | Rule::MultiplicativeExpression
+            | Rule::PowerExpression
+            | Rule::UpdateExpression
+            | Rule::UnaryExpression
+            | Rule::LeftHandSideExpression
+            | Rule::MemberExpression
+            | Rule::MemberAccessor
+            | Rule::PrimaryExpression
+    );
+    if !wrapper {
+        return false;
+    }
+    let mut nodes = inner.clone().filter(|pair| !matches!(pair.as_rule(), Rule::COMMENT | Rule::WHITESPACE));
+    matches!((nodes.next(), nodes.next()), (Some(node), None) if node.as_rule() != Rule::Semi)
+}
+
+/// Identifiers and keywords, which are interned
+fn is_name(rule: Rule) -> bool {
+    matches!(
+        rule,
+        Rule::Identifier
+            | Rule::True
+            | Rule::False
+            | Rule::Null
+            | Rule::If
+            | Rule::Else
+            | Rule::Var
+            | Rule::Global
+            | Rule::In
+            | Rule::As
+            | Rule::For
+            | Rule::While
+            | Rule::Do
+            | Rule::And
+            | Rule::Or
+            | Rule::Xor
+            | Rule::Not
+            | Rule::TypeOf
+            | Rule::InstanceOf
+            | Rule::Function
+            | Rule::Return
+            | Rule::Break
+            | Rule::Continue
+            | Rule::Class
+            | Rule::Extends
+            | Rule::Constructor
+            | Rule::Private
+            | Rule::Public
+            | Rule::Protected
+            | Rule::Static
+            | Rule::Include
+            | Rule::This
+            | Rule::Super
+            | Rule::New
+            | Rule::Implements
+            | Rule::Interface
+            | Rule::Enum
+            | Rule::Switch
+            | Rule::Case
+            | Rule::Default
+    )
+}
+
+/// A node of an [`Ast`], whatever its rule
+#[derive(Clone, Copy)]
+pub struct Node<'a> {
+    ast: &'a Ast<'a>,
+    id: NodeId,
+}
+
+impl<'a> Node<'a> {
+    fn data(self) -> &'a NodeData {
+        &self.ast.nodes[self.id.0 as usize]
+    }
+
+    pub fn ast(self) -> &'a Ast<'a> {
+        self.ast
+    }
+
+    pub fn id(self) -> NodeId {
+        self.id
+    }
+
+    pub fn rule(self) -> Rule {
+        self.data().rule
+    }
+
+    /// Without the comments the node ends with
+    pub fn span(self) -> Span {
+        let data = self.data();
+        Span { start: data.start as usize, end: data.end as usize }
+    }
+
+    pub fn text(self) -> &'a str {
+        let span = self.span();
+        &self.ast.source[span.start..span.end]
+    }
+
+    /// The interned text of an identifier or a keyword
+    pub fn symbol(self) -> Option<Symbol> {
+        self.data().symbol
+    }
+
+    /// The nodes right under this one, comments included
+    pub fn children(self) -> Children<'a> {
+        Children { ast: self.ast, next: self.id.0 + 1, end: self.data().subtree_end }
+    }
+
+    /// All the nodes under this one, each before its children
+    pub fn descendants(self) -> impl Iterator<Item = Node<'a>> + 'a {
+        let ast = self.ast;
+        (self.id.0 + 1..self.data().subtree_end).map(move |id| Node { ast, id: NodeId(id) })
+    }
+
+    /// The first child that is a `T`
+    pub fn child<T: TypedNode<'a>>(self) -> Option<T> {
+        self.children().find_map(T::cast)
+    }
+
+    /// The children that are `T`s
+    pub fn children_of<T: TypedNode<'a> + 'a>(self) -> impl Iterator<Item = T> + 'a {
+        self.children().filter_map(T::cast)
+    }
+
+    fn child_with(self, rule: Rule) -> Option<Node<'a>> {
+        self.children().find(|child| child.rule() == rule)
+    }
+
+    fn has(self, rule: Rule) -> bool {
+        self.child_with(rule).is_some()
+    }
+
+    /// The children after the first one of `rule`
+    fn after(self, rule: Rule) -> impl Iterator<Item = Node<'a>> + 'a {
+        self.children().skip_while(move |child| child.rule() != rule).skip(1)
+    }
+
+    /// The children before the first one of `rule`
+    fn before(self, rule: Rule) -> impl Iterator<Item = Node<'a>> + 'a {
+        self.children().take_while(move |child| child.rule() != rule)
+    }
+}
+
+impl fmt::Debug for Node<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("rule", &self.rule())
+            .field("span", &self.span())
+            .finish()
+    }
+}
+
+impl PartialEq for Node<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.ast, other.ast) && self.id == other.id
+    }
+}
+
+impl Eq for Node<'_> {}
+
+impl Hash for Node<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// The iterator of [`Node::children`]
+#[derive(Debug, Clone)]
+pub struct Children<'a> {
+    ast: &'a Ast<'a>,
+    next: u32,
+    end: u32,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let node = Node { ast: self.ast, id: NodeId(self.next) };
+        self.next = node.data().subtree_end;
+        Some(node)
+    }
+}
+
+/// A node of a given kind, a typed view of a [`Node`]
+pub trait TypedNode<'a>: Copy {
+    /// The node as a `Self`, `None` if it is of another kind
+    fn cast(node: Node<'a>) -> Option<Self>;
+
+    fn node(self) -> Node<'a>;
+
+    fn span(self) -> Span {
+        self.node().span()
+    }
+
+    fn text(self) -> &'a str {
+        self.node().text()
+    }
+}
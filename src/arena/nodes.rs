@@ -0,0 +1,904 @@
+use std::borrow::Cow;
+use crate::arena::{Node, Symbol, TypedNode};
+use crate::ast::number::{evaluate_number, NumberValue};
+use crate::ast::string::decode_string;
+use crate::lsv4::Rule;
+
+/// Defines typed nodes over the nodes of the given rules
+macro_rules! typed_nodes {
+    ($($(#[$attribute:meta])* $name:ident = $($rule:ident)|+;)*) => {$(
+        $(#[$attribute])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name<'a>(Node<'a>);
+
+        impl<'a> TypedNode<'a> for $name<'a> {
+            fn cast(node: Node<'a>) -> Option<Self> {
+                matches!(node.rule(), $(Rule::$rule)|+).then_some($name(node))
+            }
+
+            fn node(self) -> Node<'a> {
+                self.0
+            }
+        }
+    )*};
+}
+
+/// Defines typed nodes that are one of several others, tried in order
+macro_rules! typed_enums {
+    ($($(#[$attribute:meta])* $name:ident { $($variant:ident($node:ident),)* })*) => {$(
+        $(#[$attribute])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name<'a> {
+            $($variant($node<'a>),)*
+        }
+
+        impl<'a> TypedNode<'a> for $name<'a> {
+            fn cast(node: Node<'a>) -> Option<Self> {
+                $(if let Some(node) = $node::cast(node) {
+                    return Some($name::$variant(node));
+                })*
+                None
+            }
+
+            fn node(self) -> Node<'a> {
+                match self {
+                    $($name::$variant(node) => node.node(),)*
+                }
+            }
+        }
+    )*};
+}
+
+typed_nodes! {
+    Root = lsv4_root;
+    VariableDeclaration = VariableDeclaration;
+    FunctionDeclaration = FunctionDeclaration;
+    ClassDeclaration = ClassDeclaration;
+    PropertyDeclaration = PropertyDeclaration;
+    MethodDeclaration = MethodDeclaration;
+    ConstructorDeclaration = ConstructorDeclaration;
+    InterfaceDeclaration = InterfaceDeclaration;
+    MethodSignature = MethodSignature;
+    PropertySignature = PropertySignature;
+    EnumDeclaration = EnumDeclaration;
+    EnumMember = EnumMember;
+    IfStatement = IfStatement;
+    SwitchStatement = SwitchStatement;
+    CaseClause = CaseClause;
+    DefaultClause = DefaultClause;
+    ForStatement = ForStatement;
+    /// `for (key : value in iterable)`, the key being optional
+    ForIn = ForInCond;
+    /// `for (init; condition; update)`
+    ForCounter = ForInitCondInc;
+    /// The declaration starting a [`ForCounter`]
+    ForDeclaration = ForDeclaration;
+    WhileStatement = WhileStatement;
+    DoWhileStatement = DoWhileStatement;
+    BlockStatement = BlockStatement;
+    IncludeStatement = IncludeStatement;
+    ExpressionStatement = ExpressionStatement;
+    ReturnStatement = ReturnStatement;
+    BreakStatement = BreakStatement;
+    ContinueStatement = ContinueStatement;
+    /// A `;` alone, as a statement or as a member of a class or an interface
+    EmptyStatement = Statement | ClassStatement | InterfaceMember;
+    ErrorStatement = ErrorStatement;
+    MissingStatement = MissingStatement;
+    Parameter = Parameter;
+    Type = Type;
+    BaseType = BaseType;
+    AssignmentExpression = AssignmentExpression;
+    AnonymousFunction = AnonymousFunction;
+    LambdaFunction = LambdaFunction;
+    /// `condition ? consequent : alternative`
+    TernaryExpression = TernaryExpression;
+    /// Operands separated by the operators of a precedence level, left associative except `**`
+    BinaryExpression = LogicalORExpression | LogicalXORExpression | LogicalANDExpression | EqualityExpression
+        | RelationalExpression | BooleanORExpression | BooleanXORExpression | BooleanANDExpression
+        | ShiftExpression | AdditiveExpression | MultiplicativeExpression | PowerExpression;
+    PreUpdateExpression = PreUpdateExpression;
+    PostUpdateExpression = PostUpdateExpression;
+    /// An operand with a unary operator in front of it
+    UnaryExpression = UnaryExpression;
+    /// An expression followed by accessors, as `a.b[0](1)`
+    MemberExpression = MemberExpression;
+    ArrayAccessor = ArrayAccessor;
+    ObjectAccessor = ObjectAccessor;
+    CallAccessor = CallAccessor;
+    NullableAccessor = NullableAccessor;
+    Identifier = Identifier;
+    This = This;
+    Super = Super;
+    /// `class`, the class of the current method
+    ClassReference = Class;
+    NewExpression = NewExpression;
+    Number = Number;
+    StringLiteral = String;
+    /// `∞` or `π`
+    Constant = Constant;
+    Boolean = Boolean;
+    Null = Null;
+    Array = Array;
+    Set = Set;
+    Map = Map;
+    Object = Object;
+    KeyValuePair = KeyValuePair;
+    Interval = Interval;
+    ParenthesizedExpression = ParenthesizedExpression;
+    ErrorExpression = ErrorExpression;
+    MissingExpression = MissingExpression;
+    /// Any operator, as `+=`, `||`, `instanceof` or `++`
+    Operator = AssignmentOperator | LogicalOR | LogicalXOR | LogicalAND | BooleanOR | BooleanXOR
+        | BooleanAND | EqualityOperator | RelationalOperator | AdditiveOperator | ShiftOperator
+        | MultiplicativeOperator | PowerOperator | UnaryOperator | UpdateOperator;
+    Keyword = True | False | Null | If | Else | Var | Global | In | As | For | While | Do | And | Or
+        | Xor | Not | TypeOf | InstanceOf | Function | Return | Break | Continue | Class | Extends
+        | Constructor | Private | Public | Protected | Static | Include | This | Super | New
+        | Implements | Interface | Enum | Switch | Case | Default;
+}
+
+typed_enums! {
+    Statement {
+        VariableDeclaration(VariableDeclaration),
+        FunctionDeclaration(FunctionDeclaration),
+        ClassDeclaration(ClassDeclaration),
+        EnumDeclaration(EnumDeclaration),
+        InterfaceDeclaration(InterfaceDeclaration),
+        If(IfStatement),
+        Switch(SwitchStatement),
+        For(ForStatement),
+        While(WhileStatement),
+        DoWhile(DoWhileStatement),
+        Block(BlockStatement),
+        Include(IncludeStatement),
+        Expression(ExpressionStatement),
+        Return(ReturnStatement),
+        Break(BreakStatement),
+        Continue(ContinueStatement),
+        Empty(EmptyStatement),
+        Error(ErrorStatement),
+        Missing(MissingStatement),
+    }
+
+    Expression {
+        Assignment(AssignmentExpression),
+        AnonymousFunction(AnonymousFunction),
+        Lambda(LambdaFunction),
+        Ternary(TernaryExpression),
+        Binary(BinaryExpression),
+        PreUpdate(PreUpdateExpression),
+        PostUpdate(PostUpdateExpression),
+        Unary(UnaryExpression),
+        Member(MemberExpression),
+        Identifier(Identifier),
+        This(This),
+        Super(Super),
+        Class(ClassReference),
+        New(NewExpression),
+        Number(Number),
+        String(StringLiteral),
+        Constant(Constant),
+        Boolean(Boolean),
+        Null(Null),
+        Array(Array),
+        Set(Set),
+        Map(Map),
+        Object(Object),
+        Interval(Interval),
+        Parenthesized(ParenthesizedExpression),
+        Error(ErrorExpression),
+        Missing(MissingExpression),
+    }
+
+    ClassMember {
+        Constructor(ConstructorDeclaration),
+        Method(MethodDeclaration),
+        Property(PropertyDeclaration),
+        Empty(EmptyStatement),
+    }
+
+    InterfaceMember {
+        Method(MethodSignature),
+        Property(PropertySignature),
+        Empty(EmptyStatement),
+    }
+
+    SwitchClause {
+        Case(CaseClause),
+        Default(DefaultClause),
+    }
+
+    ForHeader {
+        In(ForIn),
+        Counter(ForCounter),
+    }
+
+    ForInit {
+        Declaration(ForDeclaration),
+        Expression(Expression),
+    }
+
+    LambdaBody {
+        Block(BlockStatement),
+        Expression(Expression),
+    }
+
+    MemberAccessor {
+        Index(ArrayAccessor),
+        Property(ObjectAccessor),
+        Call(CallAccessor),
+        Nullable(NullableAccessor),
+    }
+}
+
+/// The children the grammar always gives a node
+fn required<T>(node: Option<T>) -> T {
+    node.expect("the grammar requires this node")
+}
+
+/// The type written in front of a name, `var` giving none
+fn declared_type(node: Node) -> Option<Type> {
+    node.child().or_else(|| node.child_with(Rule::VarDec)?.child())
+}
+
+/// The type written in front of `function` or the name of a method, or after the parameters
+fn return_type(node: Node) -> Option<Type> {
+    node.child().or_else(|| node.child_with(Rule::ReturnType)?.child())
+}
+
+fn parameters<'a>(node: Node<'a>) -> impl Iterator<Item = Parameter<'a>> + 'a {
+    node.child_with(Rule::Parameters).into_iter().flat_map(Node::children_of)
+}
+
+/// The children of the child of `rule`
+fn list<'a, T: TypedNode<'a> + 'a>(node: Node<'a>, rule: Rule) -> impl Iterator<Item = T> + 'a {
+    node.child_with(rule).into_iter().flat_map(Node::children_of)
+}
+
+impl<'a> Root<'a> {
+    pub fn statements(self) -> impl Iterator<Item = Statement<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> VariableDeclaration<'a> {
+    /// Declared with `global` rather than `var` or a type
+    pub fn is_global(self) -> bool {
+        required(self.0.child_with(Rule::VarDeclKeyword)).has(Rule::Global)
+    }
+
+    pub fn declared_type(self) -> Option<Type<'a>> {
+        declared_type(required(self.0.child_with(Rule::VarDeclKeyword)))
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Eq).find_map(Expression::cast)
+    }
+}
+
+impl<'a> FunctionDeclaration<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        parameters(self.0)
+    }
+
+    pub fn return_type(self) -> Option<Type<'a>> {
+        return_type(self.0)
+    }
+
+    pub fn body(self) -> BlockStatement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ClassDeclaration<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    /// The class after `extends`
+    pub fn parent(self) -> Option<Identifier<'a>> {
+        self.0.child_with(Rule::ClassExtension)?.child()
+    }
+
+    /// The interfaces after `implements`
+    pub fn interfaces(self) -> impl Iterator<Item = Identifier<'a>> + 'a {
+        self.0.child_with(Rule::ClassImplementation).into_iter().flat_map(|node| list(node, Rule::InterfaceList))
+    }
+
+    pub fn members(self) -> impl Iterator<Item = ClassMember<'a>> + 'a {
+        list(self.0, Rule::ClassBody)
+    }
+}
+
+impl<'a> PropertyDeclaration<'a> {
+    /// `private`, `public` or `protected`
+    pub fn visibility(self) -> Option<Keyword<'a>> {
+        self.0.child_with(Rule::Visibility)?.child()
+    }
+
+    pub fn is_static(self) -> bool {
+        self.0.has(Rule::Static)
+    }
+
+    pub fn declared_type(self) -> Option<Type<'a>> {
+        self.0.child()
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Eq).find_map(Expression::cast)
+    }
+}
+
+impl<'a> MethodDeclaration<'a> {
+    /// `private`, `public` or `protected`
+    pub fn visibility(self) -> Option<Keyword<'a>> {
+        self.0.child_with(Rule::Visibility)?.child()
+    }
+
+    pub fn is_static(self) -> bool {
+        self.0.has(Rule::Static)
+    }
+
+    pub fn return_type(self) -> Option<Type<'a>> {
+        return_type(self.0)
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        parameters(self.0)
+    }
+
+    pub fn body(self) -> BlockStatement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ConstructorDeclaration<'a> {
+    /// `private`, `public` or `protected`
+    pub fn visibility(self) -> Option<Keyword<'a>> {
+        self.0.child_with(Rule::Visibility)?.child()
+    }
+
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        parameters(self.0)
+    }
+
+    pub fn body(self) -> BlockStatement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> InterfaceDeclaration<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    /// The interfaces after `extends`
+    pub fn parents(self) -> impl Iterator<Item = Identifier<'a>> + 'a {
+        self.0.child_with(Rule::InterfaceExtension).into_iter().flat_map(|node| list(node, Rule::InterfaceList))
+    }
+
+    pub fn members(self) -> impl Iterator<Item = InterfaceMember<'a>> + 'a {
+        list(self.0, Rule::InterfaceBody)
+    }
+}
+
+impl<'a> MethodSignature<'a> {
+    pub fn return_type(self) -> Option<Type<'a>> {
+        return_type(self.0)
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        parameters(self.0)
+    }
+}
+
+impl<'a> PropertySignature<'a> {
+    pub fn declared_type(self) -> Option<Type<'a>> {
+        self.0.child()
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> EnumDeclaration<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn members(self) -> impl Iterator<Item = EnumMember<'a>> + 'a {
+        list(self.0, Rule::EnumMembers)
+    }
+}
+
+impl<'a> EnumMember<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Eq).find_map(Expression::cast)
+    }
+}
+
+impl<'a> IfStatement<'a> {
+    pub fn condition(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn then_branch(self) -> Statement<'a> {
+        required(self.0.child())
+    }
+
+    pub fn else_branch(self) -> Option<Statement<'a>> {
+        self.0.after(Rule::Else).find_map(Statement::cast)
+    }
+}
+
+impl<'a> SwitchStatement<'a> {
+    pub fn condition(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn clauses(self) -> impl Iterator<Item = SwitchClause<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> CaseClause<'a> {
+    pub fn value(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn statements(self) -> impl Iterator<Item = Statement<'a>> + 'a {
+        self.0.after(Rule::Colon).filter_map(Statement::cast)
+    }
+}
+
+impl<'a> DefaultClause<'a> {
+    pub fn statements(self) -> impl Iterator<Item = Statement<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> ForStatement<'a> {
+    pub fn header(self) -> ForHeader<'a> {
+        required(self.0.child())
+    }
+
+    pub fn body(self) -> Statement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ForIn<'a> {
+    /// The name before `:`
+    pub fn key(self) -> Option<Identifier<'a>> {
+        self.0.has(Rule::Colon).then(|| required(self.0.child()))
+    }
+
+    /// The type in front of the key, `var` giving none
+    pub fn key_type(self) -> Option<Type<'a>> {
+        match self.0.has(Rule::Colon) {
+            true => declared_type(self.0.before(Rule::Colon).find(|node| node.rule() == Rule::VarDec)?),
+            false => None,
+        }
+    }
+
+    pub fn value(self) -> Identifier<'a> {
+        required(self.0.before(Rule::In).filter_map(Identifier::cast).last())
+    }
+
+    /// The type in front of the value, `var` giving none
+    pub fn value_type(self) -> Option<Type<'a>> {
+        let keyed = self.0.has(Rule::Colon);
+        let mut value = self.0.children().skip_while(|node| keyed && node.rule() != Rule::Colon);
+        declared_type(value.find(|node| node.rule() == Rule::VarDec)?)
+    }
+
+    pub fn iterable(self) -> Expression<'a> {
+        required(self.0.after(Rule::In).find_map(Expression::cast))
+    }
+}
+
+impl<'a> ForCounter<'a> {
+    pub fn init(self) -> Option<ForInit<'a>> {
+        self.0.before(Rule::Semi).find_map(ForInit::cast)
+    }
+
+    pub fn condition(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Semi).take_while(|node| node.rule() != Rule::Semi).find_map(Expression::cast)
+    }
+
+    pub fn update(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Semi).skip_while(|node| node.rule() != Rule::Semi).find_map(Expression::cast)
+    }
+}
+
+impl<'a> ForDeclaration<'a> {
+    /// Declared with `global` rather than `var` or a type
+    pub fn is_global(self) -> bool {
+        required(self.0.child_with(Rule::VarDeclKeyword)).has(Rule::Global)
+    }
+
+    pub fn declared_type(self) -> Option<Type<'a>> {
+        declared_type(required(self.0.child_with(Rule::VarDeclKeyword)))
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Option<Expression<'a>> {
+        self.0.after(Rule::Eq).find_map(Expression::cast)
+    }
+}
+
+impl<'a> WhileStatement<'a> {
+    pub fn condition(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn body(self) -> Statement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> DoWhileStatement<'a> {
+    pub fn body(self) -> Statement<'a> {
+        required(self.0.child())
+    }
+
+    pub fn condition(self) -> Expression<'a> {
+        required(self.0.after(Rule::While).find_map(Expression::cast))
+    }
+}
+
+impl<'a> BlockStatement<'a> {
+    pub fn statements(self) -> impl Iterator<Item = Statement<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> IncludeStatement<'a> {
+    pub fn path(self) -> StringLiteral<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ExpressionStatement<'a> {
+    pub fn expression(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ReturnStatement<'a> {
+    pub fn value(self) -> Option<Expression<'a>> {
+        self.0.child()
+    }
+}
+
+impl<'a> Parameter<'a> {
+    /// Passed by reference, with `@`
+    pub fn is_reference(self) -> bool {
+        self.0.has(Rule::Reference)
+    }
+
+    pub fn declared_type(self) -> Option<Type<'a>> {
+        self.0.child()
+    }
+
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> Type<'a> {
+    /// The types separated by `|`
+    pub fn alternatives(self) -> impl Iterator<Item = BaseType<'a>> + 'a {
+        self.0.children_of()
+    }
+
+    /// Ends with `?`
+    pub fn is_nullable(self) -> bool {
+        self.0.has(Rule::QuestionMark)
+    }
+}
+
+impl<'a> BaseType<'a> {
+    pub fn name(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    /// The types between `<` and `>`
+    pub fn arguments(self) -> impl Iterator<Item = Type<'a>> + 'a {
+        list(self.0, Rule::SubType)
+    }
+
+    /// The type after the arrow between `<` and `>`, as in `Function<integer => string>`
+    pub fn return_type(self) -> Option<Type<'a>> {
+        self.0.child_with(Rule::SubType)?.child_with(Rule::ReturnType)?.child()
+    }
+}
+
+impl<'a> AssignmentExpression<'a> {
+    pub fn target(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn operator(self) -> Operator<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Expression<'a> {
+        required(self.0.after(Rule::AssignmentOperator).find_map(Expression::cast))
+    }
+}
+
+impl<'a> AnonymousFunction<'a> {
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        parameters(self.0)
+    }
+
+    pub fn return_type(self) -> Option<Type<'a>> {
+        return_type(self.0)
+    }
+
+    pub fn body(self) -> BlockStatement<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> LambdaFunction<'a> {
+    /// A single parameter, or the ones between the parentheses
+    pub fn parameters(self) -> impl Iterator<Item = Parameter<'a>> + 'a {
+        let list = self.0.child_with(Rule::LambdaParameters);
+        list.into_iter().flat_map(|node| node.child().into_iter().chain(parameters(node)))
+    }
+
+    /// Only written in front of a block
+    pub fn return_type(self) -> Option<Type<'a>> {
+        self.0.child()
+    }
+
+    pub fn body(self) -> LambdaBody<'a> {
+        required(self.0.after(Rule::Arrow).find_map(LambdaBody::cast))
+    }
+}
+
+impl<'a> TernaryExpression<'a> {
+    pub fn condition(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn consequent(self) -> Expression<'a> {
+        required(self.0.after(Rule::QuestionMark).find_map(Expression::cast))
+    }
+
+    pub fn alternative(self) -> Expression<'a> {
+        required(self.0.after(Rule::Colon).find_map(Expression::cast))
+    }
+}
+
+impl<'a> BinaryExpression<'a> {
+    /// One more than the operators, `operators[i]` being between `operands[i]` and
+    /// `operands[i + 1]`. The right operand of `**` is itself a `**` expression.
+    pub fn operands(self) -> impl Iterator<Item = Expression<'a>> + 'a {
+        self.0.children_of()
+    }
+
+    pub fn operators(self) -> impl Iterator<Item = Operator<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> PreUpdateExpression<'a> {
+    pub fn operator(self) -> Operator<'a> {
+        required(self.0.child())
+    }
+
+    pub fn operand(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> PostUpdateExpression<'a> {
+    pub fn operand(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn operator(self) -> Operator<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> UnaryExpression<'a> {
+    pub fn operator(self) -> Operator<'a> {
+        required(self.0.child())
+    }
+
+    pub fn operand(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> MemberExpression<'a> {
+    pub fn object(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn accessors(self) -> impl Iterator<Item = MemberAccessor<'a>> + 'a {
+        self.0.children_of()
+    }
+}
+
+impl<'a> ArrayAccessor<'a> {
+    pub fn index(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+}
+
+impl<'a> ObjectAccessor<'a> {
+    /// The name of the property, which can be `class`
+    pub fn name(self) -> &'a str {
+        self.property().text()
+    }
+
+    pub fn symbol(self) -> Symbol {
+        required(self.property().symbol())
+    }
+
+    fn property(self) -> Node<'a> {
+        required(self.0.child_with(Rule::Identifier).or_else(|| self.0.child_with(Rule::Class)))
+    }
+}
+
+impl<'a> CallAccessor<'a> {
+    pub fn arguments(self) -> impl Iterator<Item = Expression<'a>> + 'a {
+        list(self.0, Rule::CallArguments)
+    }
+}
+
+impl<'a> Identifier<'a> {
+    pub fn symbol(self) -> Symbol {
+        required(self.0.symbol())
+    }
+
+    pub fn name(self) -> &'a str {
+        self.0.text()
+    }
+}
+
+impl<'a> Keyword<'a> {
+    pub fn symbol(self) -> Symbol {
+        required(self.0.symbol())
+    }
+}
+
+impl<'a> NewExpression<'a> {
+    pub fn class(self) -> Identifier<'a> {
+        required(self.0.child())
+    }
+
+    /// Missing in `new A`
+    pub fn arguments(self) -> Option<CallAccessor<'a>> {
+        self.0.child()
+    }
+}
+
+impl Number<'_> {
+    /// `None` for a malformed literal, which the validation reports
+    pub fn value(self) -> Option<NumberValue> {
+        evaluate_number(self.0.text()).0
+    }
+}
+
+impl<'a> StringLiteral<'a> {
+    /// The content of the string with its escape sequences decoded, borrowed from the source
+    /// when there are none
+    pub fn value(self) -> Cow<'a, str> {
+        let raw = self.0.text();
+        match raw.contains('\\') {
+            true => Cow::Owned(decode_string(raw).0),
+            false => Cow::Borrowed(&raw[1..raw.len() - 1]),
+        }
+    }
+}
+
+impl Constant<'_> {
+    pub fn value(self) -> f64 {
+        match self.0.has(Rule::PI) {
+            true => std::f64::consts::PI,
+            false => f64::INFINITY,
+        }
+    }
+}
+
+impl Boolean<'_> {
+    pub fn value(self) -> bool {
+        self.0.has(Rule::True)
+    }
+}
+
+impl<'a> Array<'a> {
+    pub fn elements(self) -> impl Iterator<Item = Expression<'a>> + 'a {
+        list(self.0, Rule::ArrayElements)
+    }
+}
+
+impl<'a> Set<'a> {
+    pub fn elements(self) -> impl Iterator<Item = Expression<'a>> + 'a {
+        list(self.0, Rule::ArrayElements)
+    }
+}
+
+impl<'a> Map<'a> {
+    /// Nothing in `[:]`
+    pub fn entries(self) -> impl Iterator<Item = KeyValuePair<'a>> + 'a {
+        list(self.0, Rule::KeyValuePairs)
+    }
+}
+
+impl<'a> Object<'a> {
+    pub fn entries(self) -> impl Iterator<Item = KeyValuePair<'a>> + 'a {
+        list(self.0, Rule::KeyValuePairs)
+    }
+}
+
+impl<'a> KeyValuePair<'a> {
+    pub fn key(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn value(self) -> Expression<'a> {
+        required(self.0.after(Rule::Colon).find_map(Expression::cast))
+    }
+}
+
+impl<'a> Interval<'a> {
+    pub fn start(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+
+    pub fn end(self) -> Expression<'a> {
+        required(self.0.after(Rule::DotDot).find_map(Expression::cast))
+    }
+
+    /// Opens with `[` rather than `]`
+    pub fn includes_start(self) -> bool {
+        self.0.children().next().is_some_and(|born| born.text() == "[")
+    }
+
+    /// Closes with `]` rather than `[`
+    pub fn includes_end(self) -> bool {
+        self.0.children().filter(|node| node.rule() == Rule::IntervalBorn).last().is_some_and(|born| born.text() == "]")
+    }
+}
+
+impl<'a> ParenthesizedExpression<'a> {
+    pub fn expression(self) -> Expression<'a> {
+        required(self.0.child())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::block_statement::BlockStatement;
+use crate::ast::function_declaration::{FunctionKeyword, Parameters};
+use crate::ast::type_annotation::ReturnType;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `function (parameters) => type { ... }`, a function used as a value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnonymousFunction {
+    pub function_keyword: AstNode<FunctionKeyword>,
+    pub lparen: AstNode<LParen>,
+    pub parameters: Option<AstNode<Parameters>>,
+    pub rparen: AstNode<RParen>,
+    pub return_type: Option<AstNode<ReturnType>>,
+    pub block_statement: AstNode<BlockStatement>,
+}
+
+node_from_pest!(AnonymousFunction, AnonymousFunction, { function_keyword, lparen, parameters, rparen, return_type, block_statement });
+
+impl PrintAst for AnonymousFunction {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.function_keyword.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        if let Some(parameters) = &self.parameters {
+            result.push_str(&parameters.print_ast(print_properties));
+        }
+        result.push_str(&self.rparen.print_ast(print_properties));
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
+        result.push_str(&self.block_statement.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for AnonymousFunction {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.function_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.parameters.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.block_statement.visit_nodes(visitor);
+    }
+}
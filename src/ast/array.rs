@@ -0,0 +1,99 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::Comma;
+use crate::ast::member_expression::{LSBracket, RSBracket};
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// `[a, b, c]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Array {
+    pub lsbracket: AstNode<LSBracket>,
+    pub elements: Option<AstNode<ArrayElements>>,
+    pub rsbracket: AstNode<RSBracket>,
+}
+
+node_from_pest!(Array, Array, { lsbracket, elements, rsbracket });
+
+impl PrintAst for Array {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lsbracket.print_ast(print_properties));
+        if let Some(elements) = &self.elements {
+            result.push_str(&elements.print_ast(print_properties));
+        }
+        result.push_str(&self.rsbracket.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for Array {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lsbracket.visit_nodes(visitor);
+        self.elements.visit_nodes(visitor);
+        self.rsbracket.visit_nodes(visitor);
+    }
+}
+
+/// The comma separated elements of an array or a set
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayElements {
+    pub expressions: Vec<AstNode<Expression>>,
+    pub commas: Vec<AstNode<Comma>>,
+}
+
+impl FromPest<'_> for ArrayElements {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::ArrayElements {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut expressions = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::Expression => expressions.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "ArrayElements"))),
+            };
+        }
+
+        Ok(ArrayElements {
+            expressions,
+            commas,
+        })
+    }
+}
+
+impl PrintAst for ArrayElements {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for (expression, comma) in self.expressions.iter().zip(self.commas.iter()) {
+            result.push_str(&expression.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last element, which doesn't have a comma after it
+        if let Some(expression) = self.expressions.last() {
+            result.push_str(&expression.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ArrayElements {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.expressions.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::expression::Expression;
+use crate::ast::member_expression::MemberExpression;
+use crate::ast::operators::AssignmentOperator;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssignmentExpression {
+    pub left_hand_side: AstNode<LeftHandSideExpression>,
+    pub operator: AstNode<AssignmentOperator>,
+    pub expression: AstNode<Expression>,
+}
+
+node_from_pest!(AssignmentExpression, AssignmentExpression, { left_hand_side, operator, expression });
+
+impl PrintAst for AssignmentExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.left_hand_side.print_ast(print_properties));
+        result.push_str(&self.operator.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for AssignmentExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.left_hand_side.visit_nodes(visitor);
+        self.operator.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeftHandSideExpression {
+    pub member_expression: AstNode<MemberExpression>,
+}
+
+node_from_pest!(LeftHandSideExpression, LeftHandSideExpression, { member_expression });
+
+impl PrintAst for LeftHandSideExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        self.member_expression.print_ast(print_properties)
+    }
+}
+
+impl VisitNodes for LeftHandSideExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.member_expression.visit_nodes(visitor);
+    }
+}
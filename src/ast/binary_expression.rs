@@ -0,0 +1,116 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::AstNode;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
+use crate::ast::operators::{AdditiveOperator, BooleanAndOperator, BooleanOrOperator, BooleanXorOperator, EqualityOperator, LogicalAndOperator, LogicalOrOperator, LogicalXorOperator, MultiplicativeOperator, PowerOperator, RelationalOperator, ShiftOperator};
+use crate::ast::update_expression::UpdateExpression;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// Defines a node for one precedence level of the form `Operand ~ (Operator ~ Operand)*`.
+///
+/// `operands` always holds one more element than `operators`: `operators[i]` sits between
+/// `operands[i]` and `operands[i + 1]`. The operators of a level are left associative.
+macro_rules! binary_expression {
+    ($name:ident, $rule:ident, $operand:ty, $operator:ty) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name {
+            pub operands: Vec<AstNode<$operand>>,
+            pub operators: Vec<AstNode<$operator>>,
+        }
+
+        impl FromPest<'_> for $name {
+            type Rule = Rule;
+            type FatalError = ConversionFailure;
+
+            fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+                let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+                if current_rule.as_rule() != Rule::$rule {
+                    return Err(ConversionError::NoMatch);
+                }
+                current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+                let mut context = current_rule.clone().into_inner();
+
+                let mut operands = vec![required(&current_rule, &mut context)?];
+                let mut operators = Vec::new();
+                while let Some(operator) = Option::from_pest(&mut context)? {
+                    operators.push(operator);
+                    operands.push(required(&current_rule, &mut context)?);
+                }
+
+                Ok($name {
+                    operands,
+                    operators,
+                })
+            }
+        }
+
+        impl PrintAst for $name {
+            fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+                let mut result = String::new();
+                if let Some(operand) = self.operands.first() {
+                    result.push_str(&operand.print_ast(print_properties));
+                }
+                for (operator, operand) in self.operators.iter().zip(self.operands.iter().skip(1)) {
+                    result.push_str(&operator.print_ast(print_properties));
+                    result.push_str(&operand.print_ast(print_properties));
+                }
+                result
+            }
+        }
+
+        impl VisitNodes for $name {
+            fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+                self.operands.visit_nodes(visitor);
+                self.operators.visit_nodes(visitor);
+            }
+        }
+    };
+}
+
+binary_expression!(LogicalOrExpression, LogicalORExpression, LogicalXorExpression, LogicalOrOperator);
+binary_expression!(LogicalXorExpression, LogicalXORExpression, LogicalAndExpression, LogicalXorOperator);
+binary_expression!(LogicalAndExpression, LogicalANDExpression, EqualityExpression, LogicalAndOperator);
+binary_expression!(EqualityExpression, EqualityExpression, RelationalExpression, EqualityOperator);
+binary_expression!(RelationalExpression, RelationalExpression, BooleanOrExpression, RelationalOperator);
+binary_expression!(BooleanOrExpression, BooleanORExpression, BooleanXorExpression, BooleanOrOperator);
+binary_expression!(BooleanXorExpression, BooleanXORExpression, BooleanAndExpression, BooleanXorOperator);
+binary_expression!(BooleanAndExpression, BooleanANDExpression, ShiftExpression, BooleanAndOperator);
+binary_expression!(ShiftExpression, ShiftExpression, AdditiveExpression, ShiftOperator);
+binary_expression!(AdditiveExpression, AdditiveExpression, MultiplicativeExpression, AdditiveOperator);
+binary_expression!(MultiplicativeExpression, MultiplicativeExpression, PowerExpression, MultiplicativeOperator);
+
+/// `base ** exponent`. Unlike the other levels `**` is right associative, so the exponent is
+/// itself a power expression: `a ** b ** c` is `a ** (b ** c)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PowerExpression {
+    pub base: AstNode<UpdateExpression>,
+    pub operator: Option<AstNode<PowerOperator>>,
+    pub exponent: Option<AstNode<PowerExpression>>,
+}
+
+node_from_pest!(PowerExpression, PowerExpression, { base, operator, exponent });
+
+impl PrintAst for PowerExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.base.print_ast(print_properties));
+        if let Some(operator) = &self.operator {
+            result.push_str(&operator.print_ast(print_properties));
+        }
+        if let Some(exponent) = &self.exponent {
+            result.push_str(&exponent.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for PowerExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.base.visit_nodes(visitor);
+        self.operator.visit_nodes(visitor);
+        self.exponent.visit_nodes(visitor);
+    }
+}
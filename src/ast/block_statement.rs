@@ -1,18 +1,18 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::statement::Statement;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::BlockStatement))]
 pub struct BlockStatement {
     pub lbrace: AstNode<Lbrace>,
     pub statements: Vec<AstNode<Statement>>,
     pub rbrace: AstNode<Rbrace>,
 }
 
+node_from_pest!(BlockStatement, BlockStatement, { lbrace, statements, rbrace });
+
 impl PrintAst for BlockStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -25,24 +25,40 @@ impl PrintAst for BlockStatement {
     }
 }
 
+impl VisitNodes for BlockStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lbrace.visit_nodes(visitor);
+        self.statements.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::LBrace))]
 pub struct Lbrace;
 
+node_from_pest!(Lbrace, LBrace);
+
 impl PrintAst for Lbrace {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("{")
     }
 }
 
+impl VisitNodes for Lbrace {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::RBrace))]
 pub struct Rbrace;
 
+node_from_pest!(Rbrace, RBrace);
+
 impl PrintAst for Rbrace {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("}")
     }
+}
+
+impl VisitNodes for Rbrace {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -1,17 +1,17 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::semi::Semi;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::BreakStatement))]
 pub struct BreakStatement {
     pub break_keyword: AstNode<BreakKeyword>,
     pub semi: Option<AstNode<Semi>>,
 }
 
+node_from_pest!(BreakStatement, BreakStatement, { break_keyword, semi });
+
 impl PrintAst for BreakStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -23,13 +23,24 @@ impl PrintAst for BreakStatement {
     }
 }
 
+impl VisitNodes for BreakStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.break_keyword.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Break))]
 pub struct BreakKeyword;
 
+node_from_pest!(BreakKeyword, Break);
+
 impl PrintAst for BreakKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("break")
     }
+}
+
+impl VisitNodes for BreakKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
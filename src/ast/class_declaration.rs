@@ -0,0 +1,488 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::block_statement::{BlockStatement, Lbrace, Rbrace};
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::Parameters;
+use crate::ast::identifier::Identifier;
+use crate::ast::interface_declaration::InterfaceList;
+use crate::ast::semi::Semi;
+use crate::ast::type_annotation::{ReturnType, TypeAnnotation};
+use crate::ast::variable_declaration::Equal;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassDeclaration {
+    pub class_keyword: AstNode<ClassKeyword>,
+    pub identifier: AstNode<Identifier>,
+    pub class_extension: Option<AstNode<ClassExtension>>,
+    pub class_implementation: Option<AstNode<ClassImplementation>>,
+    pub class_body: AstNode<ClassBody>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(ClassDeclaration, ClassDeclaration, { class_keyword, identifier, class_extension, class_implementation, class_body, semi });
+
+impl PrintAst for ClassDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.class_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(class_extension) = &self.class_extension {
+            result.push_str(&class_extension.print_ast(print_properties));
+        }
+        if let Some(class_implementation) = &self.class_implementation {
+            result.push_str(&class_implementation.print_ast(print_properties));
+        }
+        result.push_str(&self.class_body.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ClassDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.class_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.class_extension.visit_nodes(visitor);
+        self.class_implementation.visit_nodes(visitor);
+        self.class_body.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// `extends Parent`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassExtension {
+    pub extends_keyword: AstNode<ExtendsKeyword>,
+    pub identifier: AstNode<Identifier>,
+}
+
+node_from_pest!(ClassExtension, ClassExtension, { extends_keyword, identifier });
+
+impl PrintAst for ClassExtension {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.extends_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ClassExtension {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.extends_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+    }
+}
+
+/// `implements Comparable, Printable`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassImplementation {
+    pub implements_keyword: AstNode<ImplementsKeyword>,
+    pub interfaces: AstNode<InterfaceList>,
+}
+
+node_from_pest!(ClassImplementation, ClassImplementation, { implements_keyword, interfaces });
+
+impl PrintAst for ClassImplementation {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.implements_keyword.print_ast(print_properties));
+        result.push_str(&self.interfaces.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ClassImplementation {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.implements_keyword.visit_nodes(visitor);
+        self.interfaces.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassBody {
+    pub lbrace: AstNode<Lbrace>,
+    pub class_statements: Vec<AstNode<ClassStatement>>,
+    pub rbrace: AstNode<Rbrace>,
+}
+
+node_from_pest!(ClassBody, ClassBody, { lbrace, class_statements, rbrace });
+
+impl PrintAst for ClassBody {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lbrace.print_ast(print_properties));
+        for class_statement in &self.class_statements {
+            result.push_str(&class_statement.print_ast(print_properties));
+        }
+        result.push_str(&self.rbrace.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ClassBody {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lbrace.visit_nodes(visitor);
+        self.class_statements.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClassStatement {
+    ConstructorDeclaration(AstNode<ConstructorDeclaration>),
+    MethodDeclaration(AstNode<MethodDeclaration>),
+    PropertyDeclaration(AstNode<PropertyDeclaration>),
+    EmptyStatement(AstNode<Semi>),
+}
+
+node_from_pest!(ClassStatement, ClassStatement, enum { ConstructorDeclaration, MethodDeclaration, PropertyDeclaration, EmptyStatement });
+
+impl PrintAst for ClassStatement {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            ClassStatement::ConstructorDeclaration(constructor_declaration) => constructor_declaration.print_ast(print_properties),
+            ClassStatement::MethodDeclaration(method_declaration) => method_declaration.print_ast(print_properties),
+            ClassStatement::PropertyDeclaration(property_declaration) => property_declaration.print_ast(print_properties),
+            ClassStatement::EmptyStatement(semi) => semi.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for ClassStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            ClassStatement::ConstructorDeclaration(node) => node.visit_nodes(visitor),
+            ClassStatement::MethodDeclaration(node) => node.visit_nodes(visitor),
+            ClassStatement::PropertyDeclaration(node) => node.visit_nodes(visitor),
+            ClassStatement::EmptyStatement(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// A field of the class, such as `private static integer count = 0;`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertyDeclaration {
+    pub visibility: Option<AstNode<Visibility>>,
+    pub static_keyword: Option<AstNode<StaticKeyword>>,
+    pub type_annotation: Option<AstNode<TypeAnnotation>>,
+    pub identifier: AstNode<Identifier>,
+    pub equal: Option<AstNode<Equal>>,
+    pub expression: Option<AstNode<Expression>>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(PropertyDeclaration, PropertyDeclaration, { visibility, static_keyword, type_annotation, identifier, equal, expression, semi });
+
+impl PropertyDeclaration {
+    pub fn is_static(&self) -> bool {
+        self.static_keyword.is_some()
+    }
+}
+
+impl PrintAst for PropertyDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(visibility) = &self.visibility {
+            result.push_str(&visibility.print_ast(print_properties));
+        }
+        if let Some(static_keyword) = &self.static_keyword {
+            result.push_str(&static_keyword.print_ast(print_properties));
+        }
+        if let Some(type_annotation) = &self.type_annotation {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(equal) = &self.equal {
+            result.push_str(&equal.print_ast(print_properties));
+        }
+        if let Some(expression) = &self.expression {
+            result.push_str(&expression.print_ast(print_properties));
+        }
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for PropertyDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.visibility.visit_nodes(visitor);
+        self.static_keyword.visit_nodes(visitor);
+        self.type_annotation.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.equal.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// A method of the class. The type before the name, if any, is the return type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodDeclaration {
+    pub visibility: Option<AstNode<Visibility>>,
+    pub static_keyword: Option<AstNode<StaticKeyword>>,
+    /// The return type written before the name
+    pub type_annotation: Option<AstNode<TypeAnnotation>>,
+    pub identifier: AstNode<Identifier>,
+    pub lparen: AstNode<LParen>,
+    pub parameters: Option<AstNode<Parameters>>,
+    pub rparen: AstNode<RParen>,
+    /// The return type written after the parameters, `=> integer`
+    pub return_type: Option<AstNode<ReturnType>>,
+    pub block_statement: AstNode<BlockStatement>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(MethodDeclaration, MethodDeclaration, { visibility, static_keyword, type_annotation, identifier, lparen, parameters, rparen, return_type, block_statement, semi });
+
+impl MethodDeclaration {
+    pub fn is_static(&self) -> bool {
+        self.static_keyword.is_some()
+    }
+
+    /// The declared return type, whichever way it is written
+    pub fn declared_return_type(&self) -> Option<&AstNode<TypeAnnotation>> {
+        self.type_annotation.as_ref()
+            .or(self.return_type.as_ref().map(|return_type| &return_type.data.type_annotation))
+    }
+}
+
+impl PrintAst for MethodDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(visibility) = &self.visibility {
+            result.push_str(&visibility.print_ast(print_properties));
+        }
+        if let Some(static_keyword) = &self.static_keyword {
+            result.push_str(&static_keyword.print_ast(print_properties));
+        }
+        if let Some(type_annotation) = &self.type_annotation {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        if let Some(parameters) = &self.parameters {
+            result.push_str(&parameters.print_ast(print_properties));
+        }
+        result.push_str(&self.rparen.print_ast(print_properties));
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
+        result.push_str(&self.block_statement.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for MethodDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.visibility.visit_nodes(visitor);
+        self.static_keyword.visit_nodes(visitor);
+        self.type_annotation.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.parameters.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.block_statement.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstructorDeclaration {
+    pub visibility: Option<AstNode<Visibility>>,
+    pub constructor_keyword: AstNode<ConstructorKeyword>,
+    pub lparen: AstNode<LParen>,
+    pub parameters: Option<AstNode<Parameters>>,
+    pub rparen: AstNode<RParen>,
+    pub block_statement: AstNode<BlockStatement>,
+}
+
+node_from_pest!(ConstructorDeclaration, ConstructorDeclaration, { visibility, constructor_keyword, lparen, parameters, rparen, block_statement });
+
+impl PrintAst for ConstructorDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(visibility) = &self.visibility {
+            result.push_str(&visibility.print_ast(print_properties));
+        }
+        result.push_str(&self.constructor_keyword.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        if let Some(parameters) = &self.parameters {
+            result.push_str(&parameters.print_ast(print_properties));
+        }
+        result.push_str(&self.rparen.print_ast(print_properties));
+        result.push_str(&self.block_statement.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ConstructorDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.visibility.visit_nodes(visitor);
+        self.constructor_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.parameters.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.block_statement.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    Private(AstNode<PrivateKeyword>),
+    Public(AstNode<PublicKeyword>),
+    Protected(AstNode<ProtectedKeyword>),
+}
+
+node_from_pest!(Visibility, Visibility, enum { Private, Public, Protected });
+
+impl PrintAst for Visibility {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            Visibility::Private(private_keyword) => private_keyword.print_ast(print_properties),
+            Visibility::Public(public_keyword) => public_keyword.print_ast(print_properties),
+            Visibility::Protected(protected_keyword) => protected_keyword.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Visibility {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Visibility::Private(node) => node.visit_nodes(visitor),
+            Visibility::Public(node) => node.visit_nodes(visitor),
+            Visibility::Protected(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassKeyword;
+
+node_from_pest!(ClassKeyword, Class);
+
+impl PrintAst for ClassKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("class")
+    }
+}
+
+impl VisitNodes for ClassKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExtendsKeyword;
+
+node_from_pest!(ExtendsKeyword, Extends);
+
+impl PrintAst for ExtendsKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("extends")
+    }
+}
+
+impl VisitNodes for ExtendsKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StaticKeyword;
+
+node_from_pest!(StaticKeyword, Static);
+
+impl PrintAst for StaticKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("static")
+    }
+}
+
+impl VisitNodes for StaticKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstructorKeyword;
+
+node_from_pest!(ConstructorKeyword, Constructor);
+
+impl PrintAst for ConstructorKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("constructor")
+    }
+}
+
+impl VisitNodes for ConstructorKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrivateKeyword;
+
+node_from_pest!(PrivateKeyword, Private);
+
+impl PrintAst for PrivateKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("private")
+    }
+}
+
+impl VisitNodes for PrivateKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PublicKeyword;
+
+node_from_pest!(PublicKeyword, Public);
+
+impl PrintAst for PublicKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("public")
+    }
+}
+
+impl VisitNodes for PublicKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProtectedKeyword;
+
+node_from_pest!(ProtectedKeyword, Protected);
+
+impl PrintAst for ProtectedKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("protected")
+    }
+}
+
+impl VisitNodes for ProtectedKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImplementsKeyword;
+
+node_from_pest!(ImplementsKeyword, Implements);
+
+impl PrintAst for ImplementsKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("implements")
+    }
+}
+
+impl VisitNodes for ImplementsKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -1,17 +1,17 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::semi::Semi;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::ContinueStatement))]
 pub struct ContinueStatement {
     pub continue_keyword: AstNode<ContinueKeyword>,
     pub semi: Option<AstNode<Semi>>,
 }
 
+node_from_pest!(ContinueStatement, ContinueStatement, { continue_keyword, semi });
+
 impl PrintAst for ContinueStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -23,13 +23,24 @@ impl PrintAst for ContinueStatement {
     }
 }
 
+impl VisitNodes for ContinueStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.continue_keyword.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Continue))]
 pub struct ContinueKeyword;
 
+node_from_pest!(ContinueKeyword, Continue);
+
 impl PrintAst for ContinueKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("continue")
     }
+}
+
+impl VisitNodes for ContinueKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
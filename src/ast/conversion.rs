@@ -0,0 +1,161 @@
+use std::fmt;
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::{Pair, Pairs};
+use crate::lsv4::Rule;
+use crate::utils::find_next_non_comment_or_whitespace;
+
+/// A node the grammar accepted but that the syntax tree can't represent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConversionFailure {
+    /// The rule of the node that couldn't be converted
+    pub rule: Rule,
+    /// Byte range of the node in the source
+    pub start: usize,
+    pub end: usize,
+    /// The type of syntax tree node that was being built
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ConversionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot convert `{:?}` at {}..{} into a `{}` node", self.rule, self.start, self.end, self.expected)
+    }
+}
+
+impl std::error::Error for ConversionFailure {}
+
+impl ConversionFailure {
+    /// The failure of `pair` as an `expected` node. Statements and expressions are reported with
+    /// the rule of the construct they wrap, which says more than `Statement` or `Expression`.
+    pub(crate) fn at(pair: &Pair<Rule>, expected: &'static str) -> Self {
+        let rule = match pair.as_rule() {
+            Rule::Statement | Rule::Expression => find_next_non_comment_or_whitespace(&mut pair.clone().into_inner())
+                .ok()
+                .flatten()
+                .map_or(pair.as_rule(), |inner| inner.as_rule()),
+            rule => rule,
+        };
+        // The span of a pair also covers the whitespace that follows it
+        let start = pair.as_span().start();
+        ConversionFailure {
+            rule,
+            start,
+            end: start + pair.as_str().trim_end().len(),
+            expected,
+        }
+    }
+
+    /// The failure of an `expected` node missing from the inner pairs of `pair`, at the pair
+    /// found in its place or at the end of `pair`
+    pub(crate) fn missing(pair: &Pair<Rule>, inner: &Pairs<Rule>, expected: &'static str) -> Self {
+        match find_next_non_comment_or_whitespace(&mut inner.clone()).ok().flatten() {
+            Some(next) => ConversionFailure::at(&next, expected),
+            None => {
+                let end = pair.as_span().start() + pair.as_str().trim_end().len();
+                ConversionFailure {
+                    rule: pair.as_rule(),
+                    start: end,
+                    end,
+                    expected,
+                }
+            }
+        }
+    }
+}
+
+/// Converts the next node of `inner`, the inner pairs of `pair`, which the grammar always puts there
+pub(crate) fn required<'a, T>(pair: &Pair<'a, Rule>, inner: &mut Pairs<'a, Rule>) -> Result<T, ConversionError<ConversionFailure>>
+where
+    T: FromPest<'a, Rule = Rule, FatalError = ConversionFailure>,
+{
+    T::from_pest(inner).map_err(|error| match error {
+        ConversionError::NoMatch => ConversionError::Malformed(ConversionFailure::missing(pair, inner, node_name::<T>())),
+        error => error,
+    })
+}
+
+/// Fails on the pair left in `inner` once an `expected` node is converted from it
+pub(crate) fn check_consumed(inner: &Pairs<Rule>, expected: &'static str) -> Result<(), ConversionError<ConversionFailure>> {
+    match inner.peek() {
+        Some(next) => Err(ConversionError::Malformed(ConversionFailure::at(&next, expected))),
+        None => Ok(()),
+    }
+}
+
+// The name of the node type `T`, without its path nor the `AstNode` around it
+fn node_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>().trim_end_matches('>');
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Implements `FromPest` for the node of a rule, like `#[derive(FromPest)]` but failing with a
+/// [`ConversionFailure`] at the inner pair that doesn't convert, once the pair of the rule is found.
+///
+/// A struct is converted from the inner pairs field by field, an enum from the first variant that
+/// matches them. A unit struct has nothing in its pair.
+macro_rules! node_from_pest {
+    ($name:ident, $rule:ident) => {
+        impl<'a> ::from_pest::FromPest<'a> for $name {
+            type Rule = $crate::lsv4::Rule;
+            type FatalError = $crate::ast::conversion::ConversionFailure;
+
+            fn from_pest(pest: &mut ::pest::iterators::Pairs<'a, Self::Rule>) -> Result<Self, ::from_pest::ConversionError<Self::FatalError>> {
+                let pair = pest.peek()
+                    .filter(|pair| pair.as_rule() == $crate::lsv4::Rule::$rule)
+                    .ok_or(::from_pest::ConversionError::NoMatch)?;
+                $crate::ast::conversion::check_consumed(&pair.into_inner(), stringify!($name))?;
+                pest.next();
+                Ok($name)
+            }
+        }
+    };
+    ($name:ident, $rule:ident, { $($field:ident),* $(,)? }) => {
+        impl<'a> ::from_pest::FromPest<'a> for $name {
+            type Rule = $crate::lsv4::Rule;
+            type FatalError = $crate::ast::conversion::ConversionFailure;
+
+            fn from_pest(pest: &mut ::pest::iterators::Pairs<'a, Self::Rule>) -> Result<Self, ::from_pest::ConversionError<Self::FatalError>> {
+                let pair = pest.peek()
+                    .filter(|pair| pair.as_rule() == $crate::lsv4::Rule::$rule)
+                    .ok_or(::from_pest::ConversionError::NoMatch)?;
+                let mut inner = pair.clone().into_inner();
+                let node = $name {
+                    $($field: $crate::ast::conversion::required(&pair, &mut inner)?,)*
+                };
+                $crate::ast::conversion::check_consumed(&inner, stringify!($name))?;
+                pest.next();
+                Ok(node)
+            }
+        }
+    };
+    ($name:ident, $rule:ident, enum { $($variant:ident),+ $(,)? }) => {
+        impl<'a> ::from_pest::FromPest<'a> for $name {
+            type Rule = $crate::lsv4::Rule;
+            type FatalError = $crate::ast::conversion::ConversionFailure;
+
+            fn from_pest(pest: &mut ::pest::iterators::Pairs<'a, Self::Rule>) -> Result<Self, ::from_pest::ConversionError<Self::FatalError>> {
+                let pair = pest.peek()
+                    .filter(|pair| pair.as_rule() == $crate::lsv4::Rule::$rule)
+                    .ok_or(::from_pest::ConversionError::NoMatch)?;
+                let mut inner = pair.clone().into_inner();
+                let node = 'variants: {
+                    $(
+                        match ::from_pest::FromPest::from_pest(&mut inner) {
+                            Ok(node) => break 'variants $name::$variant(node),
+                            Err(::from_pest::ConversionError::NoMatch) => {}
+                            Err(error) => return Err(error),
+                        }
+                    )+
+                    return Err(::from_pest::ConversionError::Malformed(
+                        $crate::ast::conversion::ConversionFailure::missing(&pair, &inner, stringify!($name)),
+                    ));
+                };
+                $crate::ast::conversion::check_consumed(&inner, stringify!($name))?;
+                pest.next();
+                Ok(node)
+            }
+        }
+    };
+}
+
+pub(crate) use node_from_pest;
@@ -1,15 +1,13 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::expression::Expression;
 use crate::ast::semi::Semi;
 use crate::ast::statement::Statement;
 use crate::ast::while_statement::{LParen, RParen, WhileKeyword};
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::DoWhileStatement))]
 pub struct DoWhileStatement {
     pub do_keyword: AstNode<DoKeyword>,
     pub statement: AstNode<Statement>,
@@ -20,6 +18,8 @@ pub struct DoWhileStatement {
     pub semi: Option<AstNode<Semi>>,
 }
 
+node_from_pest!(DoWhileStatement, DoWhileStatement, { do_keyword, statement, while_keyword, lparen, expression, rparen, semi });
+
 impl PrintAst for DoWhileStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -36,13 +36,29 @@ impl PrintAst for DoWhileStatement {
     }
 }
 
+impl VisitNodes for DoWhileStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.do_keyword.visit_nodes(visitor);
+        self.statement.visit_nodes(visitor);
+        self.while_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Do))]
 pub struct DoKeyword;
 
+node_from_pest!(DoKeyword, Do);
+
 impl PrintAst for DoKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("do")
     }
+}
+
+impl VisitNodes for DoKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -0,0 +1,159 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::block_statement::{Lbrace, Rbrace};
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::Comma;
+use crate::ast::identifier::Identifier;
+use crate::ast::semi::Semi;
+use crate::ast::variable_declaration::Equal;
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// `enum Direction { UP, DOWN = 4 }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumDeclaration {
+    pub enum_keyword: AstNode<EnumKeyword>,
+    pub identifier: AstNode<Identifier>,
+    pub lbrace: AstNode<Lbrace>,
+    pub members: Option<AstNode<EnumMembers>>,
+    pub rbrace: AstNode<Rbrace>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(EnumDeclaration, EnumDeclaration, { enum_keyword, identifier, lbrace, members, rbrace, semi });
+
+impl PrintAst for EnumDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.enum_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result.push_str(&self.lbrace.print_ast(print_properties));
+        if let Some(members) = &self.members {
+            result.push_str(&members.print_ast(print_properties));
+        }
+        result.push_str(&self.rbrace.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for EnumDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.enum_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.lbrace.visit_nodes(visitor);
+        self.members.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumMembers {
+    pub members: Vec<AstNode<EnumMember>>,
+    pub commas: Vec<AstNode<Comma>>,
+}
+
+impl FromPest<'_> for EnumMembers {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::EnumMembers {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut members = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::EnumMember => members.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "EnumMembers"))),
+            };
+        }
+
+        Ok(EnumMembers {
+            members,
+            commas,
+        })
+    }
+}
+
+impl PrintAst for EnumMembers {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for (member, comma) in self.members.iter().zip(self.commas.iter()) {
+            result.push_str(&member.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last member, which doesn't have a comma after it
+        if let Some(member) = self.members.last() {
+            result.push_str(&member.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for EnumMembers {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.members.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+    }
+}
+
+/// A constant of the enum, with an optional explicit value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumMember {
+    pub identifier: AstNode<Identifier>,
+    pub equal: Option<AstNode<Equal>>,
+    pub expression: Option<AstNode<Expression>>,
+}
+
+node_from_pest!(EnumMember, EnumMember, { identifier, equal, expression });
+
+impl PrintAst for EnumMember {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(equal) = &self.equal {
+            result.push_str(&equal.print_ast(print_properties));
+        }
+        if let Some(expression) = &self.expression {
+            result.push_str(&expression.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for EnumMember {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.identifier.visit_nodes(visitor);
+        self.equal.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumKeyword;
+
+node_from_pest!(EnumKeyword, Enum);
+
+impl PrintAst for EnumKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("enum")
+    }
+}
+
+impl VisitNodes for EnumKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
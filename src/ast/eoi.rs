@@ -1,14 +1,18 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::EOI))]
 pub struct Eoi;
 
+node_from_pest!(Eoi, EOI);
+
 impl PrintAst for Eoi {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::new()
     }
+}
+
+impl VisitNodes for Eoi {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -1,19 +1,25 @@
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
-use pest_ast::FromPest;
+use crate::ast::conversion::{check_consumed, node_from_pest, ConversionFailure};
 use crate::ast::AstNode;
+use crate::ast::anonymous_function::AnonymousFunction;
+use crate::ast::assignment_expression::AssignmentExpression;
+use crate::ast::lambda_function::LambdaFunction;
+use crate::ast::recovery::{ErrorExpression, MissingExpression};
 use crate::ast::semi::Semi;
-use crate::utils::PrintAst;
+use crate::ast::ternary_expression::TernaryExpression;
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 use crate::Rule;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::ExpressionStatement))]
 pub struct ExpressionStatement {
     pub expression: AstNode<Expression>,
     pub semi: Option<AstNode<Semi>>,
 }
 
+node_from_pest!(ExpressionStatement, ExpressionStatement, { expression, semi });
+
 impl PrintAst for ExpressionStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -25,27 +31,76 @@ impl PrintAst for ExpressionStatement {
     }
 }
 
+impl VisitNodes for ExpressionStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.expression.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Expression;
+pub enum Expression {
+    Assignment(AstNode<AssignmentExpression>),
+    AnonymousFunction(AstNode<AnonymousFunction>),
+    Lambda(AstNode<LambdaFunction>),
+    Ternary(AstNode<TernaryExpression>),
+    /// Only built by the recovering parser
+    Error(AstNode<ErrorExpression>),
+    Missing(AstNode<MissingExpression>),
+}
+
+impl PrintAst for Expression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            Expression::Assignment(assignment) => assignment.print_ast(print_properties),
+            Expression::AnonymousFunction(anonymous_function) => anonymous_function.print_ast(print_properties),
+            Expression::Lambda(lambda) => lambda.print_ast(print_properties),
+            Expression::Ternary(ternary) => ternary.print_ast(print_properties),
+            Expression::Error(error) => error.print_ast(print_properties),
+            Expression::Missing(missing) => missing.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Expression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Expression::Assignment(node) => node.visit_nodes(visitor),
+            Expression::AnonymousFunction(node) => node.visit_nodes(visitor),
+            Expression::Lambda(node) => node.visit_nodes(visitor),
+            Expression::Ternary(node) => node.visit_nodes(visitor),
+            Expression::Error(node) => node.visit_nodes(visitor),
+            Expression::Missing(node) => node.visit_nodes(visitor),
+        }
+    }
+}
 
 impl FromPest<'_> for Expression {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
-    fn from_pest(pest: &mut Pairs<Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
-        if pest.peek().unwrap().as_rule() != Rule::Expression {
-            return Err(ConversionError::NoMatch);
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        match current_rule.as_rule() {
+            Rule::Expression => {}
+            // The recovering parser puts these where an expression is expected
+            Rule::ErrorExpression => return Ok(Expression::Error(AstNode::from_pest(pest)?)),
+            Rule::MissingExpression => return Ok(Expression::Missing(AstNode::from_pest(pest)?)),
+            _ => return Err(ConversionError::NoMatch),
         }
-        pest.next();
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        Ok(Expression)
-    }
-}
+        let mut context = current_rule.clone().into_inner();
 
-impl PrintAst for Expression {
-    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
-        let mut result = String::new();
-        result.push_str("TODO Expression");
-        result
+        let next = find_next_non_comment_or_whitespace(&mut context)?.map(|next| next.as_rule());
+        let expression = match next {
+            Some(Rule::AssignmentExpression) => Expression::Assignment(AstNode::from_pest(&mut context)?),
+            Some(Rule::AnonymousFunction) => Expression::AnonymousFunction(AstNode::from_pest(&mut context)?),
+            Some(Rule::LambdaFunction) => Expression::Lambda(AstNode::from_pest(&mut context)?),
+            Some(Rule::TernaryExpression) => Expression::Ternary(AstNode::from_pest(&mut context)?),
+            _ => return Err(ConversionError::Malformed(ConversionFailure::missing(&current_rule, &context, "Expression"))),
+        };
+        check_consumed(&context, "Expression")?;
+        Ok(expression)
     }
-}
\ No newline at end of file
+}
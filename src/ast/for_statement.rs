@@ -0,0 +1,314 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::expression::Expression;
+use crate::ast::identifier::Identifier;
+use crate::ast::semi::Semi;
+use crate::ast::statement::Statement;
+use crate::ast::ternary_expression::Colon;
+use crate::ast::variable_declaration::{Equal, VarDecKeyword, VarDeclKeyword};
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForStatement {
+    pub for_keyword: AstNode<ForKeyword>,
+    pub lparen: AstNode<LParen>,
+    pub for_inner_cond: AstNode<ForInnerCond>,
+    pub rparen: AstNode<RParen>,
+    pub statement: AstNode<Statement>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(ForStatement, ForStatement, { for_keyword, lparen, for_inner_cond, rparen, statement, semi });
+
+impl PrintAst for ForStatement {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.for_keyword.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        result.push_str(&self.for_inner_cond.print_ast(print_properties));
+        result.push_str(&self.rparen.print_ast(print_properties));
+        result.push_str(&self.statement.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ForStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.for_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.for_inner_cond.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.statement.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// What is between the parentheses of a `for`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ForInnerCond {
+    In(AstNode<ForInCond>),
+    InitCondInc(AstNode<ForInitCondInc>),
+}
+
+node_from_pest!(ForInnerCond, ForInnerCond, enum { In, InitCondInc });
+
+impl PrintAst for ForInnerCond {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            ForInnerCond::In(for_in_cond) => for_in_cond.print_ast(print_properties),
+            ForInnerCond::InitCondInc(for_init_cond_inc) => for_init_cond_inc.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for ForInnerCond {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            ForInnerCond::In(node) => node.visit_nodes(visitor),
+            ForInnerCond::InitCondInc(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// `[var key :] var value in expression`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForInCond {
+    pub key: Option<ForInKey>,
+    pub var_dec: Option<AstNode<VarDecKeyword>>,
+    pub identifier: AstNode<Identifier>,
+    pub in_keyword: AstNode<InKeyword>,
+    pub expression: AstNode<Expression>,
+}
+
+impl FromPest<'_> for ForInCond {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::ForInCond {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        // Whether the first variable is a key or the value is only known once we see the colon
+        let mut var_dec = Option::from_pest(&mut context)?;
+        let mut identifier = required(&current_rule, &mut context)?;
+        let mut key = None;
+        if let Some(colon) = Option::from_pest(&mut context)? {
+            key = Some(ForInKey {
+                var_dec,
+                identifier,
+                colon,
+            });
+            var_dec = Option::from_pest(&mut context)?;
+            identifier = required(&current_rule, &mut context)?;
+        }
+        let in_keyword = required(&current_rule, &mut context)?;
+        let expression = required(&current_rule, &mut context)?;
+
+        Ok(ForInCond {
+            key,
+            var_dec,
+            identifier,
+            in_keyword,
+            expression,
+        })
+    }
+}
+
+impl PrintAst for ForInCond {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(key) = &self.key {
+            result.push_str(&key.print_ast(print_properties));
+        }
+        if let Some(var_dec) = &self.var_dec {
+            result.push_str(&var_dec.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result.push_str(&self.in_keyword.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ForInCond {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.key.visit_nodes(visitor);
+        self.var_dec.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.in_keyword.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+    }
+}
+
+/// The `var key :` part of a `for (var key : var value in ...)`. It is not a rule of its own in
+/// the grammar, so it carries no trivia by itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForInKey {
+    pub var_dec: Option<AstNode<VarDecKeyword>>,
+    pub identifier: AstNode<Identifier>,
+    pub colon: AstNode<Colon>,
+}
+
+impl PrintAst for ForInKey {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(var_dec) = &self.var_dec {
+            result.push_str(&var_dec.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result.push_str(&self.colon.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ForInKey {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.var_dec.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+    }
+}
+
+/// `init; condition; increment`, each part being optional
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForInitCondInc {
+    pub init: Option<AstNode<ForInit>>,
+    pub init_semi: AstNode<Semi>,
+    pub condition: Option<AstNode<Expression>>,
+    pub condition_semi: AstNode<Semi>,
+    pub increment: Option<AstNode<Expression>>,
+}
+
+node_from_pest!(ForInitCondInc, ForInitCondInc, { init, init_semi, condition, condition_semi, increment });
+
+impl PrintAst for ForInitCondInc {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(init) = &self.init {
+            result.push_str(&init.print_ast(print_properties));
+        }
+        result.push_str(&self.init_semi.print_ast(print_properties));
+        if let Some(condition) = &self.condition {
+            result.push_str(&condition.print_ast(print_properties));
+        }
+        result.push_str(&self.condition_semi.print_ast(print_properties));
+        if let Some(increment) = &self.increment {
+            result.push_str(&increment.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ForInitCondInc {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.init.visit_nodes(visitor);
+        self.init_semi.visit_nodes(visitor);
+        self.condition.visit_nodes(visitor);
+        self.condition_semi.visit_nodes(visitor);
+        self.increment.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ForInit {
+    ForDeclaration(AstNode<ForDeclaration>),
+    Expression(AstNode<Expression>),
+}
+
+node_from_pest!(ForInit, ForInit, enum { ForDeclaration, Expression });
+
+impl PrintAst for ForInit {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            ForInit::ForDeclaration(for_declaration) => for_declaration.print_ast(print_properties),
+            ForInit::Expression(expression) => expression.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for ForInit {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            ForInit::ForDeclaration(node) => node.visit_nodes(visitor),
+            ForInit::Expression(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// A variable declaration in the init of a `for`, which has no semicolon of its own
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForDeclaration {
+    pub var_decl_keyword: AstNode<VarDeclKeyword>,
+    pub identifier: AstNode<Identifier>,
+    pub equal: Option<AstNode<Equal>>,
+    pub expression: Option<AstNode<Expression>>,
+}
+
+node_from_pest!(ForDeclaration, ForDeclaration, { var_decl_keyword, identifier, equal, expression });
+
+impl PrintAst for ForDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.var_decl_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(equal) = &self.equal {
+            result.push_str(&equal.print_ast(print_properties));
+        }
+        if let Some(expression) = &self.expression {
+            result.push_str(&expression.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ForDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.var_decl_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.equal.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForKeyword;
+
+node_from_pest!(ForKeyword, For);
+
+impl PrintAst for ForKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("for")
+    }
+}
+
+impl VisitNodes for ForKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InKeyword;
+
+node_from_pest!(InKeyword, In);
+
+impl PrintAst for InKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("in")
+    }
+}
+
+impl VisitNodes for InKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -1,28 +1,44 @@
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
-use crate::ast::type_annotation::TypeAnnotation;
+use crate::ast::type_annotation::{ReturnType, TypeAnnotation};
 use crate::ast::identifier::Identifier;
-use pest_ast::FromPest;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
 use crate::ast::AstNode;
 use crate::ast::block_statement::BlockStatement;
 use crate::ast::semi::Semi;
 use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 use crate::Rule;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FunctionDeclaration {
+    /// The return type written before `function`
+    pub type_annotation: Option<AstNode<TypeAnnotation>>,
     pub function_keyword: AstNode<FunctionKeyword>,
     pub identifier: AstNode<Identifier>,
     pub lparen: AstNode<Lparen>,
     pub parameters: Option<AstNode<Parameters>>,
     pub rparen: AstNode<Rparen>,
+    /// The return type written after the parameters, `=> integer`
+    pub return_type: Option<AstNode<ReturnType>>,
     pub block_statement: AstNode<BlockStatement>,
     pub semi: Option<AstNode<Semi>>,
 }
 
+impl FunctionDeclaration {
+    /// The declared return type, whichever way it is written
+    pub fn declared_return_type(&self) -> Option<&AstNode<TypeAnnotation>> {
+        self.type_annotation.as_ref()
+            .or(self.return_type.as_ref().map(|return_type| &return_type.data.type_annotation))
+    }
+}
+
 impl PrintAst for FunctionDeclaration {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
+        if let Some(type_annotation) = &self.type_annotation {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
         result.push_str(&self.function_keyword.print_ast(print_properties));
         result.push_str(&self.identifier.print_ast(print_properties));
         result.push_str(&self.lparen.print_ast(print_properties));
@@ -30,6 +46,9 @@ impl PrintAst for FunctionDeclaration {
             result.push_str(&parameters.print_ast(print_properties));
         }
         result.push_str(&self.rparen.print_ast(print_properties));
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
         result.push_str(&self.block_statement.print_ast(print_properties));
         if let Some(semi) = &self.semi {
             result.push_str(&semi.print_ast(print_properties));
@@ -38,30 +57,51 @@ impl PrintAst for FunctionDeclaration {
     }
 }
 
+impl VisitNodes for FunctionDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.type_annotation.visit_nodes(visitor);
+        self.function_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.parameters.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.block_statement.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 impl FromPest<'_> for FunctionDeclaration {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
-        if pest.peek().unwrap().as_rule() != Rule::FunctionDeclaration {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::FunctionDeclaration {
             return Err(ConversionError::NoMatch);
         }
-        let mut context = pest.next().unwrap().into_inner();
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        let function_keyword = AstNode::from_pest(&mut context)?;
-        let identifier = AstNode::from_pest(&mut context)?;
-        let lparen = AstNode::from_pest(&mut context)?;
-        let parameters = AstNode::from_pest(&mut context).ok();
-        let rparen = AstNode::from_pest(&mut context)?;
-        let block_statement = AstNode::from_pest(&mut context)?;
-        let semi = AstNode::from_pest(&mut context).ok();
+        let mut context = current_rule.clone().into_inner();
+
+        let type_annotation = Option::from_pest(&mut context)?;
+        let function_keyword = required(&current_rule, &mut context)?;
+        let identifier = required(&current_rule, &mut context)?;
+        let lparen = required(&current_rule, &mut context)?;
+        let parameters = Option::from_pest(&mut context)?;
+        let rparen = required(&current_rule, &mut context)?;
+        let return_type = Option::from_pest(&mut context)?;
+        let block_statement = required(&current_rule, &mut context)?;
+        let semi = Option::from_pest(&mut context)?;
 
         Ok(FunctionDeclaration {
+            type_annotation,
             function_keyword,
             identifier,
             lparen,
             parameters,
             rparen,
+            return_type,
             block_statement,
             semi,
         })
@@ -69,16 +109,20 @@ impl FromPest<'_> for FunctionDeclaration {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Function))]
 pub struct FunctionKeyword;
 
+node_from_pest!(FunctionKeyword, Function);
+
 impl PrintAst for FunctionKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("function")
     }
 }
 
+impl VisitNodes for FunctionKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Parameters {
     pub parameter: Vec<AstNode<Parameter>>,
@@ -87,7 +131,7 @@ pub struct Parameters {
 
 impl FromPest<'_> for Parameters {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut parameter = Vec::new();
@@ -97,7 +141,7 @@ impl FromPest<'_> for Parameters {
             return Err(ConversionError::NoMatch);
         }
         current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
-        let mut context = &mut current_rule.clone().into_inner();
+        let mut context = current_rule.clone().into_inner();
         loop {
             let next = find_next_non_comment_or_whitespace(&mut context)?;
             if let Some(next) = next {
@@ -126,21 +170,35 @@ impl PrintAst for Parameters {
             result.push_str(&comma.print_ast(print_properties));
         }
         // Add the last parameter, which doesn't have a comma after it
-        result.push_str(&self.parameter.last().unwrap().print_ast(print_properties));
+        if let Some(parameter) = self.parameter.last() {
+            result.push_str(&parameter.print_ast(print_properties));
+        }
         result
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPest)]
-#[pest_ast(rule(Rule::Parameter))]
+impl VisitNodes for Parameters {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.parameter.visit_nodes(visitor);
+        self.comma.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Parameter {
+    pub reference: Option<AstNode<Reference>>,
     pub type_annotation: Option<AstNode<TypeAnnotation>>,
     pub identifier: AstNode<Identifier>,
 }
 
+node_from_pest!(Parameter, Parameter, { reference, type_annotation, identifier });
+
 impl PrintAst for Parameter {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
+        if let Some(reference) = &self.reference {
+            result.push_str(&reference.print_ast(print_properties));
+        }
         if let Some(type_annotation) = &self.type_annotation {
             result.push_str(&type_annotation.print_ast(print_properties));
         }
@@ -149,32 +207,71 @@ impl PrintAst for Parameter {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPest)]
-#[pest_ast(rule(Rule::Comma))]
+impl VisitNodes for Parameter {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.reference.visit_nodes(visitor);
+        self.type_annotation.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+    }
+}
+
+/// The `@` of a parameter passed by reference
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference;
+
+node_from_pest!(Reference, Reference);
+
+impl PrintAst for Reference {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("@")
+    }
+}
+
+impl VisitNodes for Reference {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Comma;
 
+node_from_pest!(Comma, Comma);
+
 impl PrintAst for Comma {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from(",")
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPest)]
-#[pest_ast(rule(Rule::LParen))]
+impl VisitNodes for Comma {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Lparen;
 
+node_from_pest!(Lparen, LParen);
+
 impl PrintAst for Lparen {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("(")
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPest)]
-#[pest_ast(rule(Rule::RParen))]
+impl VisitNodes for Lparen {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Rparen;
 
+node_from_pest!(Rparen, RParen);
+
 impl PrintAst for Rparen {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from(")")
     }
+}
+
+impl VisitNodes for Rparen {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
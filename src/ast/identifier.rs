@@ -1,21 +1,38 @@
-use pest_ast::FromPest;
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::ConversionFailure;
 use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
 use crate::Rule;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Identifier))]
 pub struct Identifier {
-    #[pest_ast(outer(with(span_into_str)))]
     pub name: String,
 }
 
+impl FromPest<'_> for Identifier {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::Identifier {
+            return Err(ConversionError::NoMatch);
+        }
+        pest.next();
+
+        Ok(Identifier {
+            name: current_rule.as_str().to_string(),
+        })
+    }
+}
+
 impl PrintAst for Identifier {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         self.name.clone()
     }
 }
 
-pub fn span_into_str(span: pest::Span) -> String {
-    span.as_str().to_string()
-}
\ No newline at end of file
+impl VisitNodes for Identifier {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
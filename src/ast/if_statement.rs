@@ -1,13 +1,14 @@
 use pest::iterators::Pairs;
-use from_pest::{ConversionError, Void};
+use from_pest::ConversionError;
 use from_pest::FromPest;
-use pest_ast::FromPest;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
 use crate::ast::AstNode;
 use crate::ast::expression::Expression;
 use crate::ast::semi::Semi;
 use crate::ast::statement::Statement;
-use crate::ast::while_statement::{LParen, RParen, WhileKeyword};
+use crate::ast::while_statement::{LParen, RParen};
 use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
 use crate::Rule;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -24,7 +25,7 @@ pub struct IfStatement {
 
 impl FromPest<'_> for IfStatement {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
@@ -33,16 +34,16 @@ impl FromPest<'_> for IfStatement {
         }
         current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        let mut context = &mut current_rule.clone().into_inner();
+        let mut context = current_rule.clone().into_inner();
 
-        let if_keyword = AstNode::from_pest(&mut context)?;
-        let lparen = AstNode::from_pest(&mut context)?;
-        let expression = AstNode::from_pest(&mut context)?;
-        let rparen = AstNode::from_pest(&mut context)?;
-        let statement = AstNode::from_pest(&mut context)?;
-        let else_keyword = AstNode::from_pest(&mut context).ok();
-        let else_statement = AstNode::from_pest(&mut context).ok();
-        let semi = AstNode::from_pest(&mut context).ok();
+        let if_keyword = required(&current_rule, &mut context)?;
+        let lparen = required(&current_rule, &mut context)?;
+        let expression = required(&current_rule, &mut context)?;
+        let rparen = required(&current_rule, &mut context)?;
+        let statement = required(&current_rule, &mut context)?;
+        let else_keyword = Option::from_pest(&mut context)?;
+        let else_statement = Option::from_pest(&mut context)?;
+        let semi = Option::from_pest(&mut context)?;
 
         Ok(IfStatement {
             if_keyword,
@@ -78,24 +79,45 @@ impl PrintAst for IfStatement {
     }
 }
 
+impl VisitNodes for IfStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.if_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.statement.visit_nodes(visitor);
+        self.else_keyword.visit_nodes(visitor);
+        self.else_statement.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::If))]
 pub struct IfKeyword;
 
+node_from_pest!(IfKeyword, If);
+
 impl PrintAst for IfKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("if")
     }
 }
 
+impl VisitNodes for IfKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Else))]
 pub struct ElseKeyword;
 
+node_from_pest!(ElseKeyword, Else);
+
 impl PrintAst for ElseKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("else")
     }
+}
+
+impl VisitNodes for ElseKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
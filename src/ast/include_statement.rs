@@ -0,0 +1,65 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::semi::Semi;
+use crate::ast::string::StringLiteral;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `include("folder/file")`, inserting the statements of another file of the AI
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncludeStatement {
+    pub include_keyword: AstNode<IncludeKeyword>,
+    pub lparen: AstNode<LParen>,
+    pub path: AstNode<StringLiteral>,
+    pub rparen: AstNode<RParen>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(IncludeStatement, IncludeStatement, { include_keyword, lparen, path, rparen, semi });
+
+impl IncludeStatement {
+    /// The included path, escape sequences decoded
+    pub fn path(&self) -> &str {
+        &self.path.data.value
+    }
+}
+
+impl PrintAst for IncludeStatement {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.include_keyword.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        result.push_str(&self.path.print_ast(print_properties));
+        result.push_str(&self.rparen.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for IncludeStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.include_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.path.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncludeKeyword;
+
+node_from_pest!(IncludeKeyword, Include);
+
+impl PrintAst for IncludeKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("include")
+    }
+}
+
+impl VisitNodes for IncludeKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -0,0 +1,288 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::block_statement::{Lbrace, Rbrace};
+use crate::ast::class_declaration::ExtendsKeyword;
+use crate::ast::function_declaration::{Comma, Parameters};
+use crate::ast::identifier::Identifier;
+use crate::ast::semi::Semi;
+use crate::ast::type_annotation::{ReturnType, TypeAnnotation};
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceDeclaration {
+    pub interface_keyword: AstNode<InterfaceKeyword>,
+    pub identifier: AstNode<Identifier>,
+    pub interface_extension: Option<AstNode<InterfaceExtension>>,
+    pub interface_body: AstNode<InterfaceBody>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(InterfaceDeclaration, InterfaceDeclaration, { interface_keyword, identifier, interface_extension, interface_body, semi });
+
+impl PrintAst for InterfaceDeclaration {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.interface_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(interface_extension) = &self.interface_extension {
+            result.push_str(&interface_extension.print_ast(print_properties));
+        }
+        result.push_str(&self.interface_body.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for InterfaceDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.interface_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.interface_extension.visit_nodes(visitor);
+        self.interface_body.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// `extends Parent, Other`, an interface can extend several interfaces
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceExtension {
+    pub extends_keyword: AstNode<ExtendsKeyword>,
+    pub interfaces: AstNode<InterfaceList>,
+}
+
+node_from_pest!(InterfaceExtension, InterfaceExtension, { extends_keyword, interfaces });
+
+impl PrintAst for InterfaceExtension {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.extends_keyword.print_ast(print_properties));
+        result.push_str(&self.interfaces.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for InterfaceExtension {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.extends_keyword.visit_nodes(visitor);
+        self.interfaces.visit_nodes(visitor);
+    }
+}
+
+/// The comma separated names after `extends` in an interface or `implements` in a class
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceList {
+    pub identifiers: Vec<AstNode<Identifier>>,
+    pub commas: Vec<AstNode<Comma>>,
+}
+
+impl FromPest<'_> for InterfaceList {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::InterfaceList {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut identifiers = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::Identifier => identifiers.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "InterfaceList"))),
+            };
+        }
+
+        Ok(InterfaceList {
+            identifiers,
+            commas,
+        })
+    }
+}
+
+impl PrintAst for InterfaceList {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for (identifier, comma) in self.identifiers.iter().zip(self.commas.iter()) {
+            result.push_str(&identifier.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last interface, which doesn't have a comma after it
+        if let Some(identifier) = self.identifiers.last() {
+            result.push_str(&identifier.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for InterfaceList {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.identifiers.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceBody {
+    pub lbrace: AstNode<Lbrace>,
+    pub members: Vec<AstNode<InterfaceMember>>,
+    pub rbrace: AstNode<Rbrace>,
+}
+
+node_from_pest!(InterfaceBody, InterfaceBody, { lbrace, members, rbrace });
+
+impl PrintAst for InterfaceBody {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lbrace.print_ast(print_properties));
+        for member in &self.members {
+            result.push_str(&member.print_ast(print_properties));
+        }
+        result.push_str(&self.rbrace.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for InterfaceBody {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lbrace.visit_nodes(visitor);
+        self.members.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InterfaceMember {
+    MethodSignature(AstNode<MethodSignature>),
+    PropertySignature(AstNode<PropertySignature>),
+    EmptyStatement(AstNode<Semi>),
+}
+
+node_from_pest!(InterfaceMember, InterfaceMember, enum { MethodSignature, PropertySignature, EmptyStatement });
+
+impl PrintAst for InterfaceMember {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            InterfaceMember::MethodSignature(method_signature) => method_signature.print_ast(print_properties),
+            InterfaceMember::PropertySignature(property_signature) => property_signature.print_ast(print_properties),
+            InterfaceMember::EmptyStatement(semi) => semi.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for InterfaceMember {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            InterfaceMember::MethodSignature(node) => node.visit_nodes(visitor),
+            InterfaceMember::PropertySignature(node) => node.visit_nodes(visitor),
+            InterfaceMember::EmptyStatement(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// A method the implementing classes must define, such as `integer count(item);`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodSignature {
+    pub type_annotation: Option<AstNode<TypeAnnotation>>,
+    pub identifier: AstNode<Identifier>,
+    pub lparen: AstNode<LParen>,
+    pub parameters: Option<AstNode<Parameters>>,
+    pub rparen: AstNode<RParen>,
+    pub return_type: Option<AstNode<ReturnType>>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(MethodSignature, MethodSignature, { type_annotation, identifier, lparen, parameters, rparen, return_type, semi });
+
+impl PrintAst for MethodSignature {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(type_annotation) = &self.type_annotation {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        if let Some(parameters) = &self.parameters {
+            result.push_str(&parameters.print_ast(print_properties));
+        }
+        result.push_str(&self.rparen.print_ast(print_properties));
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for MethodSignature {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.type_annotation.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.parameters.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// A property the implementing classes must have, such as `string name;`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertySignature {
+    pub type_annotation: Option<AstNode<TypeAnnotation>>,
+    pub identifier: AstNode<Identifier>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(PropertySignature, PropertySignature, { type_annotation, identifier, semi });
+
+impl PrintAst for PropertySignature {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(type_annotation) = &self.type_annotation {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for PropertySignature {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.type_annotation.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceKeyword;
+
+node_from_pest!(InterfaceKeyword, Interface);
+
+impl PrintAst for InterfaceKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("interface")
+    }
+}
+
+impl VisitNodes for InterfaceKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -0,0 +1,92 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::expression::Expression;
+use crate::ast::member_expression::{LSBracket, RSBracket};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `[start..end]`. A border is closed when its bracket faces the inside of the interval, so
+/// `]1..2[` excludes both bounds and `[1..2[` only excludes `2`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start_border: AstNode<IntervalBorder>,
+    pub start: AstNode<Expression>,
+    pub dot_dot: AstNode<DotDot>,
+    pub end: AstNode<Expression>,
+    pub end_border: AstNode<IntervalBorder>,
+}
+
+node_from_pest!(Interval, Interval, { start_border, start, dot_dot, end, end_border });
+
+impl Interval {
+    pub fn is_start_closed(&self) -> bool {
+        matches!(*self.start_border.data, IntervalBorder::LSBracket(_))
+    }
+
+    pub fn is_end_closed(&self) -> bool {
+        matches!(*self.end_border.data, IntervalBorder::RSBracket(_))
+    }
+}
+
+impl PrintAst for Interval {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.start_border.print_ast(print_properties));
+        result.push_str(&self.start.print_ast(print_properties));
+        result.push_str(&self.dot_dot.print_ast(print_properties));
+        result.push_str(&self.end.print_ast(print_properties));
+        result.push_str(&self.end_border.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for Interval {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.start_border.visit_nodes(visitor);
+        self.start.visit_nodes(visitor);
+        self.dot_dot.visit_nodes(visitor);
+        self.end.visit_nodes(visitor);
+        self.end_border.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IntervalBorder {
+    LSBracket(AstNode<LSBracket>),
+    RSBracket(AstNode<RSBracket>),
+}
+
+node_from_pest!(IntervalBorder, IntervalBorn, enum { LSBracket, RSBracket });
+
+impl PrintAst for IntervalBorder {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            IntervalBorder::LSBracket(lsbracket) => lsbracket.print_ast(print_properties),
+            IntervalBorder::RSBracket(rsbracket) => rsbracket.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for IntervalBorder {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            IntervalBorder::LSBracket(node) => node.visit_nodes(visitor),
+            IntervalBorder::RSBracket(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DotDot;
+
+node_from_pest!(DotDot, DotDot);
+
+impl PrintAst for DotDot {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("..")
+    }
+}
+
+impl VisitNodes for DotDot {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -0,0 +1,141 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::AstNode;
+use crate::ast::conversion::{check_consumed, node_from_pest, required, ConversionFailure};
+use crate::ast::block_statement::BlockStatement;
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::{Parameter, Parameters};
+use crate::ast::operators::Arrow;
+use crate::ast::type_annotation::TypeAnnotation;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// `x => x * 2`, `(a, b) -> a + b` or `(integer x) => integer { return x; }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LambdaFunction {
+    pub parameters: Option<AstNode<LambdaParameters>>,
+    pub arrow: AstNode<Arrow>,
+    /// Only allowed in front of a block body
+    pub return_type: Option<AstNode<TypeAnnotation>>,
+    pub body: AstNode<LambdaBody>,
+}
+
+node_from_pest!(LambdaFunction, LambdaFunction, { parameters, arrow, return_type, body });
+
+impl PrintAst for LambdaFunction {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(parameters) = &self.parameters {
+            result.push_str(&parameters.print_ast(print_properties));
+        }
+        result.push_str(&self.arrow.print_ast(print_properties));
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
+        result.push_str(&self.body.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for LambdaFunction {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.parameters.visit_nodes(visitor);
+        self.arrow.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.body.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LambdaParameters {
+    /// A single parameter without parentheses
+    Parameter(AstNode<Parameter>),
+    Parenthesized(AstNode<LParen>, Option<AstNode<Parameters>>, AstNode<RParen>),
+}
+
+impl FromPest<'_> for LambdaParameters {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::LambdaParameters {
+            return Err(ConversionError::NoMatch);
+        }
+        pest.next();
+
+        let mut context = current_rule.clone().into_inner();
+
+        let next = find_next_non_comment_or_whitespace(&mut context)?;
+        let parameters = if next.is_some_and(|next| next.as_rule() == Rule::LParen) {
+            LambdaParameters::Parenthesized(
+                required(&current_rule, &mut context)?,
+                Option::from_pest(&mut context)?,
+                required(&current_rule, &mut context)?,
+            )
+        } else {
+            LambdaParameters::Parameter(required(&current_rule, &mut context)?)
+        };
+        check_consumed(&context, "LambdaParameters")?;
+
+        Ok(parameters)
+    }
+}
+
+impl PrintAst for LambdaParameters {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            LambdaParameters::Parameter(parameter) => parameter.print_ast(print_properties),
+            LambdaParameters::Parenthesized(lparen, parameters, rparen) => {
+                let mut result = String::new();
+                result.push_str(&lparen.print_ast(print_properties));
+                if let Some(parameters) = parameters {
+                    result.push_str(&parameters.print_ast(print_properties));
+                }
+                result.push_str(&rparen.print_ast(print_properties));
+                result
+            }
+        }
+    }
+}
+
+impl VisitNodes for LambdaParameters {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            LambdaParameters::Parameter(node) => node.visit_nodes(visitor),
+            LambdaParameters::Parenthesized(l_paren, parameters, r_paren) => {
+                l_paren.visit_nodes(visitor);
+                parameters.visit_nodes(visitor);
+                r_paren.visit_nodes(visitor);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LambdaBody {
+    BlockStatement(AstNode<BlockStatement>),
+    Expression(AstNode<Expression>),
+}
+
+node_from_pest!(LambdaBody, LambdaBody, enum { BlockStatement, Expression });
+
+impl PrintAst for LambdaBody {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            LambdaBody::BlockStatement(block_statement) => block_statement.print_ast(print_properties),
+            LambdaBody::Expression(expression) => expression.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for LambdaBody {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            LambdaBody::BlockStatement(node) => node.visit_nodes(visitor),
+            LambdaBody::Expression(node) => node.visit_nodes(visitor),
+        }
+    }
+}
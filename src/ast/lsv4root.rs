@@ -1,10 +1,12 @@
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
 use crate::ast::AstNode;
+use crate::ast::conversion::{required, ConversionFailure};
 use crate::ast::eoi::Eoi;
 use crate::ast::statement::Statement;
 use crate::lsv4::Rule;
-use crate::utils::{PrintAst};
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Lsv4Root {
@@ -23,9 +25,16 @@ impl PrintAst for Lsv4Root {
     }
 }
 
+impl VisitNodes for Lsv4Root {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.statements.visit_nodes(visitor);
+        self.eoi.visit_nodes(visitor);
+    }
+}
+
 impl FromPest<'_> for Lsv4Root {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
@@ -36,24 +45,26 @@ impl FromPest<'_> for Lsv4Root {
 
         let mut statements = Vec::new();
 
-        let mut context = &mut current_rule.clone().into_inner();
+        let root = current_rule.clone();
+        let mut context = current_rule.into_inner();
 
-        // While you can convert to a statement, do so
+        // Every pair before the end of input is a statement, and each of them must convert
         loop {
-            let statement = AstNode::<Statement>::from_pest(&mut context);
-            if let Ok(statement) = statement {
-                statements.push(statement);
-            } else {
-                break;
+            let next = find_next_non_comment_or_whitespace(&mut context)?;
+            match next {
+                // The recovering parser also leaves an error statement for a stray `}`
+                Some(next) if matches!(next.as_rule(), Rule::Statement | Rule::ErrorStatement) => {
+                    statements.push(required::<AstNode<Statement>>(&root, &mut context)?);
+                }
+                _ => break,
             }
         }
 
-        // Try to get eoi
-        let eoi = Some(AstNode::<Eoi>::from_pest(&mut context)?);
+        let eoi = required(&root, &mut context)?;
 
         Ok(Lsv4Root {
             statements,
-            eoi: eoi.ok_or(ConversionError::NoMatch)?
+            eoi,
         })
     }
-}
\ No newline at end of file
+}
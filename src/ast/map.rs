@@ -0,0 +1,133 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::Comma;
+use crate::ast::member_expression::{LSBracket, RSBracket};
+use crate::ast::ternary_expression::Colon;
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// `[key: value, ...]`, or `[:]` for the empty map
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map {
+    pub lsbracket: AstNode<LSBracket>,
+    pub key_value_pairs: Option<AstNode<KeyValuePairs>>,
+    /// Only present in the empty map
+    pub colon: Option<AstNode<Colon>>,
+    pub rsbracket: AstNode<RSBracket>,
+}
+
+node_from_pest!(Map, Map, { lsbracket, key_value_pairs, colon, rsbracket });
+
+impl PrintAst for Map {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lsbracket.print_ast(print_properties));
+        if let Some(key_value_pairs) = &self.key_value_pairs {
+            result.push_str(&key_value_pairs.print_ast(print_properties));
+        }
+        if let Some(colon) = &self.colon {
+            result.push_str(&colon.print_ast(print_properties));
+        }
+        result.push_str(&self.rsbracket.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for Map {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lsbracket.visit_nodes(visitor);
+        self.key_value_pairs.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+        self.rsbracket.visit_nodes(visitor);
+    }
+}
+
+/// The comma separated entries of a map or an object
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyValuePairs {
+    pub key_value_pairs: Vec<AstNode<KeyValuePair>>,
+    pub commas: Vec<AstNode<Comma>>,
+}
+
+impl FromPest<'_> for KeyValuePairs {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::KeyValuePairs {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut key_value_pairs = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::KeyValuePair => key_value_pairs.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "KeyValuePairs"))),
+            };
+        }
+
+        Ok(KeyValuePairs {
+            key_value_pairs,
+            commas,
+        })
+    }
+}
+
+impl PrintAst for KeyValuePairs {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for (key_value_pair, comma) in self.key_value_pairs.iter().zip(self.commas.iter()) {
+            result.push_str(&key_value_pair.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last entry, which doesn't have a comma after it
+        if let Some(key_value_pair) = self.key_value_pairs.last() {
+            result.push_str(&key_value_pair.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for KeyValuePairs {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.key_value_pairs.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyValuePair {
+    pub key: AstNode<Expression>,
+    pub colon: AstNode<Colon>,
+    pub value: AstNode<Expression>,
+}
+
+node_from_pest!(KeyValuePair, KeyValuePair, { key, colon, value });
+
+impl PrintAst for KeyValuePair {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.key.print_ast(print_properties));
+        result.push_str(&self.colon.print_ast(print_properties));
+        result.push_str(&self.value.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for KeyValuePair {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.key.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+        self.value.visit_nodes(visitor);
+    }
+}
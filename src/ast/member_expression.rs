@@ -0,0 +1,330 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::class_declaration::ClassKeyword;
+use crate::ast::expression::Expression;
+use crate::ast::function_declaration::Comma;
+use crate::ast::identifier::Identifier;
+use crate::ast::primary_expression::PrimaryExpression;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemberExpression {
+    pub primary_expression: AstNode<PrimaryExpression>,
+    pub accessors: Vec<AstNode<MemberAccessor>>,
+}
+
+node_from_pest!(MemberExpression, MemberExpression, { primary_expression, accessors });
+
+impl PrintAst for MemberExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.primary_expression.print_ast(print_properties));
+        for accessor in &self.accessors {
+            result.push_str(&accessor.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for MemberExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.primary_expression.visit_nodes(visitor);
+        self.accessors.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MemberAccessor {
+    Array(AstNode<ArrayAccessor>),
+    Object(AstNode<ObjectAccessor>),
+    Call(AstNode<CallAccessor>),
+    Nullable(AstNode<NullableAccessor>),
+}
+
+node_from_pest!(MemberAccessor, MemberAccessor, enum { Array, Object, Call, Nullable });
+
+impl PrintAst for MemberAccessor {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            MemberAccessor::Array(array_accessor) => array_accessor.print_ast(print_properties),
+            MemberAccessor::Object(object_accessor) => object_accessor.print_ast(print_properties),
+            MemberAccessor::Call(call_accessor) => call_accessor.print_ast(print_properties),
+            MemberAccessor::Nullable(nullable_accessor) => nullable_accessor.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for MemberAccessor {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            MemberAccessor::Array(node) => node.visit_nodes(visitor),
+            MemberAccessor::Object(node) => node.visit_nodes(visitor),
+            MemberAccessor::Call(node) => node.visit_nodes(visitor),
+            MemberAccessor::Nullable(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// `value[expression]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayAccessor {
+    pub lsbracket: AstNode<LSBracket>,
+    pub expression: AstNode<Expression>,
+    pub rsbracket: AstNode<RSBracket>,
+}
+
+node_from_pest!(ArrayAccessor, ArrayAccessor, { lsbracket, expression, rsbracket });
+
+impl PrintAst for ArrayAccessor {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lsbracket.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result.push_str(&self.rsbracket.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ArrayAccessor {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lsbracket.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rsbracket.visit_nodes(visitor);
+    }
+}
+
+/// `value.property`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectAccessor {
+    pub dot: AstNode<Dot>,
+    pub property: Property,
+}
+
+node_from_pest!(ObjectAccessor, ObjectAccessor, { dot, property });
+
+impl PrintAst for ObjectAccessor {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.dot.print_ast(print_properties));
+        result.push_str(&self.property.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ObjectAccessor {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.dot.visit_nodes(visitor);
+        self.property.visit_nodes(visitor);
+    }
+}
+
+/// The name after the dot, `class` being a keyword but also a property
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Property {
+    Identifier(AstNode<Identifier>),
+    Class(AstNode<ClassKeyword>),
+}
+
+impl FromPest<'_> for Property {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<'_, Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        match find_next_non_comment_or_whitespace(pest)?.map(|next| next.as_rule()) {
+            Some(Rule::Identifier) => Ok(Property::Identifier(AstNode::from_pest(pest)?)),
+            Some(Rule::Class) => Ok(Property::Class(AstNode::from_pest(pest)?)),
+            _ => Err(ConversionError::NoMatch),
+        }
+    }
+}
+
+impl PrintAst for Property {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            Property::Identifier(identifier) => identifier.print_ast(print_properties),
+            Property::Class(class_keyword) => class_keyword.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Property {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Property::Identifier(node) => node.visit_nodes(visitor),
+            Property::Class(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// `value(arguments)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CallAccessor {
+    pub lparen: AstNode<LParen>,
+    pub arguments: Option<AstNode<CallArguments>>,
+    pub rparen: AstNode<RParen>,
+}
+
+node_from_pest!(CallAccessor, CallAccessor, { lparen, arguments, rparen });
+
+impl PrintAst for CallAccessor {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lparen.print_ast(print_properties));
+        if let Some(arguments) = &self.arguments {
+            result.push_str(&arguments.print_ast(print_properties));
+        }
+        result.push_str(&self.rparen.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for CallAccessor {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lparen.visit_nodes(visitor);
+        self.arguments.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CallArguments {
+    pub expressions: Vec<AstNode<Expression>>,
+    pub commas: Vec<AstNode<Comma>>,
+}
+
+impl FromPest<'_> for CallArguments {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::CallArguments {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut expressions = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::Expression => expressions.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "CallArguments"))),
+            };
+        }
+
+        Ok(CallArguments {
+            expressions,
+            commas,
+        })
+    }
+}
+
+impl PrintAst for CallArguments {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for (expression, comma) in self.expressions.iter().zip(self.commas.iter()) {
+            result.push_str(&expression.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last argument, which doesn't have a comma after it
+        if let Some(expression) = self.expressions.last() {
+            result.push_str(&expression.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for CallArguments {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.expressions.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+    }
+}
+
+/// `value!`, asserting that the value is not null
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NullableAccessor {
+    pub exclamation_mark: AstNode<ExclamationMark>,
+}
+
+node_from_pest!(NullableAccessor, NullableAccessor, { exclamation_mark });
+
+impl PrintAst for NullableAccessor {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        self.exclamation_mark.print_ast(print_properties)
+    }
+}
+
+impl VisitNodes for NullableAccessor {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.exclamation_mark.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LSBracket;
+
+node_from_pest!(LSBracket, LSBracket);
+
+impl PrintAst for LSBracket {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("[")
+    }
+}
+
+impl VisitNodes for LSBracket {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RSBracket;
+
+node_from_pest!(RSBracket, RSBracket);
+
+impl PrintAst for RSBracket {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("]")
+    }
+}
+
+impl VisitNodes for RSBracket {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dot;
+
+node_from_pest!(Dot, Dot);
+
+impl PrintAst for Dot {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from(".")
+    }
+}
+
+impl VisitNodes for Dot {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExclamationMark;
+
+node_from_pest!(ExclamationMark, ExclamationMark);
+
+impl PrintAst for ExclamationMark {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("!")
+    }
+}
+
+impl VisitNodes for ExclamationMark {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
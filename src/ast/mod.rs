@@ -1,26 +1,53 @@
 pub mod lsv4root;
+pub mod conversion;
 pub mod eoi;
 pub mod semi;
 pub mod block_statement;
 pub mod statement;
 mod return_statement;
-mod expression;
+pub mod include_statement;
+pub mod expression;
+mod assignment_expression;
+mod anonymous_function;
+mod lambda_function;
+mod ternary_expression;
+pub mod binary_expression;
+mod update_expression;
+pub mod member_expression;
+mod primary_expression;
+mod new_expression;
+mod array;
+mod set;
+mod map;
+mod object;
+mod interval;
+mod operators;
+pub mod number;
+pub mod string;
 mod break_statement;
 mod continue_statement;
 mod while_statement;
+mod for_statement;
 mod do_while_statement;
 mod if_statement;
 mod function_declaration;
+mod class_declaration;
+mod interface_declaration;
+mod enum_declaration;
+mod switch_statement;
 mod type_annotation;
 mod identifier;
 mod variable_declaration;
+pub mod recovery;
 
-use from_pest::{ConversionError, FromPest, Void};
-use pest::iterators::Pairs;
+use std::any::Any;
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::{Pair, Pairs};
+use crate::ast::conversion::ConversionFailure;
 use crate::lsv4::Rule;
 use crate::utils;
 use crate::utils::PrintAst;
-use std::fmt::Pointer;
+use crate::grow_stack;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct AstNode<T: for<'a> FromPest<'a> + PrintAst> {
@@ -30,40 +57,50 @@ pub struct AstNode<T: for<'a> FromPest<'a> + PrintAst> {
 
 impl<T: for<'a> FromPest<'a> + PrintAst + std::fmt::Debug> std::fmt::Debug for AstNode<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            return f.debug_struct("AstNode")
-                .field("data", &self.data)
-                .field("nb_children", &self.meta.prev_ignored.len())
-                .finish();
-        }
+        grow_stack(|| {
+            if f.alternate() {
+                return f.debug_struct("AstNode")
+                    .field("data", &self.data)
+                    .field("span", &self.meta.span)
+                    .field("nb_children", &self.meta.prev_ignored.len())
+                    .finish();
+            }
 
-        f.debug_struct("AstNode")
-            .field("data", &self.data)
-            .field("meta", &self.meta)
-            .finish()
+            f.debug_struct("AstNode")
+                .field("data", &self.data)
+                .field("meta", &self.meta)
+                .finish()
+        })
     }
 }
 
-impl<'a, T: for<'b> FromPest<'b, Rule = Rule, FatalError = Void> + PrintAst> FromPest<'a> for AstNode<T> {
+impl<'a, T: for<'b> FromPest<'b, Rule = Rule, FatalError = ConversionFailure> + PrintAst> FromPest<'a> for AstNode<T> {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<'a, Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut meta = AstNodeMeta {
             prev_ignored: Vec::new(),
             post_ignored: Vec::new(),
+            span: Span::default(),
         };
 
+        // Only given back to `pest` once the node is converted. Cloning pairs doesn't copy their tokens
         let mut context = pest.clone();
 
         // Extract preceding comments and whitespace
-        meta.prev_ignored = extract_comments_or_whitespace(&mut context, true)?;
+        meta.prev_ignored = extract_comments_or_whitespace(&mut context)?;
 
-        let data = Box::new(T::from_pest(&mut context)?);
+        let pair = context.peek().ok_or(ConversionError::NoMatch)?;
+        let data = Box::new(grow_stack(|| T::from_pest(&mut context))?);
+        meta.span = Span {
+            start: pair.as_span().start(),
+            end: content_end(pair),
+        };
 
         // Check if the next significant node is not a comment or whitespace
-        if is_last_significant_node(&mut context) {
-            meta.post_ignored = extract_comments_or_whitespace(&mut context, false)?;
+        if is_last_significant_node(&context) {
+            meta.post_ignored = extract_comments_or_whitespace(&mut context)?;
         }
 
         *pest = context;
@@ -72,25 +109,42 @@ impl<'a, T: for<'b> FromPest<'b, Rule = Rule, FatalError = Void> + PrintAst> Fro
     }
 }
 
-// Extracts comments or whitespace based on the direction (before or after the node)
-fn extract_comments_or_whitespace(pest: &mut Pairs<Rule>, before_node: bool) -> Result<Vec<CommentOrWhitespace>, ConversionError<Void>> {
+// Extracts the comments or whitespace at the head of the iterator
+fn extract_comments_or_whitespace(pest: &mut Pairs<Rule>) -> Result<Vec<CommentOrWhitespace>, ConversionError<ConversionFailure>> {
     let mut items = Vec::new();
 
-    while let Some(_) = pest.peek() {
-        if let Ok(comment_or_whitespace) = CommentOrWhitespace::from_pest(pest) {
-            items.push(comment_or_whitespace);
-        } else {
-            break;
-        }
+    while let Some(comment_or_whitespace) = Option::<CommentOrWhitespace>::from_pest(pest)? {
+        items.push(comment_or_whitespace);
     }
 
     Ok(items)
 }
 
-// Checks if the current position in the pest iterator is at the last significant node
-fn is_last_significant_node(pest: &mut Pairs<Rule>) -> bool {
-    let remaining = pest.clone().filter(|p| p.as_rule() != Rule::COMMENT && p.as_rule() != Rule::WHITESPACE);
-    remaining.count() == 0
+// The end of a pair without the whitespace and comments it ends with
+fn content_end(pair: Pair<Rule>) -> usize {
+    let mut end = pair.as_span().end();
+    // Walks back over the last inner pairs only: the tokens of a pair would be checked against the
+    // whole source each time in debug builds
+    let mut inner = pair.into_inner();
+    while let Some(last) = inner.next_back() {
+        utils::visit_pair();
+        if last.as_span().end() != end {
+            // Something that isn't a pair, like a string in the grammar, comes after
+            break;
+        }
+        match last.as_rule() {
+            Rule::COMMENT | Rule::WHITESPACE => end = last.as_span().start(),
+            // Ends with its last inner pair
+            _ => inner = last.into_inner(),
+        }
+    }
+    end
+}
+
+// Checks if only comments and whitespace are left in the pest iterator. Stops at the first
+// significant pair, so that converting siblings one after the other stays linear
+fn is_last_significant_node(pest: &Pairs<Rule>) -> bool {
+    pest.clone().inspect(|_| utils::visit_pair()).all(|p| p.as_rule() == Rule::COMMENT || p.as_rule() == Rule::WHITESPACE)
 }
 
 impl<T: for<'a> FromPest<'a> + PrintAst> PrintAst for AstNode<T> {
@@ -99,7 +153,7 @@ impl<T: for<'a> FromPest<'a> + PrintAst> PrintAst for AstNode<T> {
         for comment_or_whitespace in &self.meta.prev_ignored {
             result.push_str(&comment_or_whitespace.print_ast(print_properties));
         }
-        result.push_str(&self.data.print_ast(print_properties));
+        result.push_str(&grow_stack(|| self.data.print_ast(print_properties)));
         for comment_or_whitespace in &self.meta.post_ignored {
             result.push_str(&comment_or_whitespace.print_ast(print_properties));
         }
@@ -151,7 +205,7 @@ impl PrintAst for CommentOrWhitespace {
 
 impl FromPest<'_> for CommentOrWhitespace {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
@@ -163,11 +217,17 @@ impl FromPest<'_> for CommentOrWhitespace {
         let mut context = current_rule.clone().into_inner();
         let comment_or_whitespace = match current_rule.as_rule() {
             Rule::COMMENT => {
-                let comment = context.next().ok_or(ConversionError::NoMatch)?.as_str().to_string();
+                let comment = context.next()
+                    .ok_or_else(|| ConversionError::Malformed(ConversionFailure::at(&current_rule, "CommentOrWhitespace")))?
+                    .as_str()
+                    .to_string();
                 CommentOrWhitespace::Comment(comment)
             }
             Rule::WHITESPACE => {
-                let whitespace = context.next().ok_or(ConversionError::NoMatch)?.as_str().to_string();
+                let whitespace = context.next()
+                    .ok_or_else(|| ConversionError::Malformed(ConversionFailure::at(&current_rule, "CommentOrWhitespace")))?
+                    .as_str()
+                    .to_string();
                 CommentOrWhitespace::Whitespace(whitespace)
             }
             _ => return Err(ConversionError::NoMatch),
@@ -180,4 +240,64 @@ impl FromPest<'_> for CommentOrWhitespace {
 pub struct AstNodeMeta {
     pub prev_ignored: Vec<CommentOrWhitespace>,
     pub post_ignored: Vec<CommentOrWhitespace>, // Ignored after the node if it's the last node
+    pub span: Span, // Without the ignored comments and whitespace
+}
+
+impl<T: for<'a> FromPest<'a> + PrintAst> AstNode<T> {
+    pub fn span(&self) -> Span {
+        self.meta.span
+    }
+}
+
+/// Receives the metadata and the data of each node, and tells whether to visit its children
+pub type NodeVisitor<'a> = dyn FnMut(&mut AstNodeMeta, &mut dyn Any) -> bool + 'a;
+
+/// Walks the nodes of a syntax tree, each node before its children
+pub trait VisitNodes {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>);
+}
+
+impl<T: for<'a> FromPest<'a> + PrintAst + VisitNodes + 'static> VisitNodes for AstNode<T> {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        if visitor(&mut self.meta, self.data.as_mut()) {
+            grow_stack(|| self.data.visit_nodes(visitor));
+        }
+    }
+}
+
+impl<T: VisitNodes> VisitNodes for Vec<T> {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        for node in self {
+            node.visit_nodes(visitor);
+        }
+    }
+}
+
+impl<T: VisitNodes> VisitNodes for Option<T> {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        if let Some(node) = self {
+            node.visit_nodes(visitor);
+        }
+    }
+}
+
+/// A byte range in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
 }
\ No newline at end of file
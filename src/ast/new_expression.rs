@@ -0,0 +1,51 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::identifier::Identifier;
+use crate::ast::member_expression::CallAccessor;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `new Cell(x, y)`. The arguments can be left out along with the parentheses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NewExpression {
+    pub new_keyword: AstNode<NewKeyword>,
+    pub identifier: AstNode<Identifier>,
+    pub arguments: Option<AstNode<CallAccessor>>,
+}
+
+node_from_pest!(NewExpression, NewExpression, { new_keyword, identifier, arguments });
+
+impl PrintAst for NewExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.new_keyword.print_ast(print_properties));
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(arguments) = &self.arguments {
+            result.push_str(&arguments.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for NewExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.new_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.arguments.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NewKeyword;
+
+node_from_pest!(NewKeyword, New);
+
+impl PrintAst for NewKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("new")
+    }
+}
+
+impl VisitNodes for NewKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
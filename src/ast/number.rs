@@ -0,0 +1,165 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::ConversionFailure;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// A number literal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Number {
+    /// The literal as it appears in the source, prefix and `_` separators included
+    pub raw: String,
+}
+
+impl Number {
+    /// The value of the literal, or `None` if it is malformed or doesn't fit its type
+    pub fn value(&self) -> Option<NumberValue> {
+        evaluate_number(&self.raw).0
+    }
+}
+
+impl FromPest<'_> for Number {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::Number {
+            return Err(ConversionError::NoMatch);
+        }
+        pest.next();
+
+        Ok(Number {
+            raw: current_rule.as_str().to_string(),
+        })
+    }
+}
+
+impl PrintAst for Number {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        self.raw.clone()
+    }
+}
+
+impl VisitNodes for Number {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+/// The evaluated value of a number literal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Integer(i64),
+    Real(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberErrorKind {
+    /// A `_` that isn't between two digits
+    MisplacedSeparator,
+    /// A `0x` or `0b` prefix with nothing after it
+    MissingDigits,
+    /// A character that isn't a digit of the literal's base
+    InvalidDigit,
+    /// A literal made of valid characters that still isn't a number, like `1e`
+    Malformed,
+    /// An integer that doesn't fit in 64 bits
+    IntegerOverflow,
+    /// A real too large to be represented
+    RealOverflow,
+}
+
+/// A problem in a number literal, as a byte range in the raw literal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberError {
+    pub kind: NumberErrorKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Evaluates a raw number literal.
+///
+/// Decimal literals are integers unless they have a fraction or an exponent. Hexadecimal (`0x`)
+/// and binary (`0b`) literals are integers, and like in Java they may use all 64 bits, so
+/// `0xFFFFFFFFFFFFFFFF` is `-1`. Misplaced separators are reported but don't prevent the
+/// evaluation, the other problems do.
+pub fn evaluate_number(raw: &str) -> (Option<NumberValue>, Vec<NumberError>) {
+    let lowercase_prefix = raw.get(..2).map(str::to_ascii_lowercase);
+    let (prefix_len, radix) = match lowercase_prefix.as_deref() {
+        Some("0x") => (2, 16),
+        Some("0b") => (2, 2),
+        _ => (0, 10),
+    };
+    let is_digit = |c: char| c.is_digit(radix);
+
+    let mut errors = Vec::new();
+    check_separators(raw, prefix_len, is_digit, &mut errors);
+
+    let error = |kind, start, end| NumberError { kind, start, end };
+    let digits = &raw[prefix_len..];
+    let invalid_digit = |allowed: &dyn Fn(char) -> bool| {
+        digits.char_indices()
+            .find(|(_, c)| *c != '_' && !allowed(*c))
+            .map(|(index, c)| error(NumberErrorKind::InvalidDigit, prefix_len + index, prefix_len + index + c.len_utf8()))
+    };
+    let cleaned: String = digits.chars().filter(|c| *c != '_').collect();
+
+    let value = if radix != 10 {
+        if cleaned.is_empty() {
+            Err(error(NumberErrorKind::MissingDigits, 0, raw.len()))
+        } else if let Some(invalid) = invalid_digit(&is_digit) {
+            Err(invalid)
+        } else {
+            u64::from_str_radix(&cleaned, radix)
+                .map(|value| NumberValue::Integer(value as i64))
+                .map_err(|_| error(NumberErrorKind::IntegerOverflow, 0, raw.len()))
+        }
+    } else if cleaned.chars().all(|c| c.is_ascii_digit()) {
+        cleaned.parse::<i64>()
+            .map(NumberValue::Integer)
+            .map_err(|_| error(NumberErrorKind::IntegerOverflow, 0, raw.len()))
+    } else if let Some(invalid) = invalid_digit(&|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')) {
+        Err(invalid)
+    } else {
+        match cleaned.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(NumberValue::Real(value)),
+            Ok(_) => Err(error(NumberErrorKind::RealOverflow, 0, raw.len())),
+            Err(_) => Err(error(NumberErrorKind::Malformed, 0, raw.len())),
+        }
+    };
+
+    match value {
+        Ok(value) => (Some(value), errors),
+        Err(value_error) => {
+            errors.push(value_error);
+            (None, errors)
+        }
+    }
+}
+
+/// Reports each run of `_` that doesn't sit between two digits, the prefix not being a digit
+fn check_separators(raw: &str, prefix_len: usize, is_digit: impl Fn(char) -> bool, errors: &mut Vec<NumberError>) {
+    let mut chars = raw.char_indices().skip(prefix_len).peekable();
+    let mut previous = None;
+    while let Some((start, c)) = chars.next() {
+        if c != '_' {
+            previous = Some(c);
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some((_, '_')) = chars.peek() {
+            chars.next();
+            end += 1;
+        }
+        let next = chars.peek().map(|(_, c)| *c);
+        let between_digits = previous.is_some_and(&is_digit) && next.is_some_and(&is_digit);
+        if between_digits && end == start + 1 {
+            continue;
+        }
+        errors.push(NumberError {
+            kind: NumberErrorKind::MisplacedSeparator,
+            start,
+            end,
+        });
+    }
+}
@@ -0,0 +1,36 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::block_statement::{Lbrace, Rbrace};
+use crate::ast::map::KeyValuePairs;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `{key: value, ...}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Object {
+    pub lbrace: AstNode<Lbrace>,
+    pub key_value_pairs: Option<AstNode<KeyValuePairs>>,
+    pub rbrace: AstNode<Rbrace>,
+}
+
+node_from_pest!(Object, Object, { lbrace, key_value_pairs, rbrace });
+
+impl PrintAst for Object {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lbrace.print_ast(print_properties));
+        if let Some(key_value_pairs) = &self.key_value_pairs {
+            result.push_str(&key_value_pairs.print_ast(print_properties));
+        }
+        result.push_str(&self.rbrace.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for Object {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lbrace.visit_nodes(visitor);
+        self.key_value_pairs.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+    }
+}
@@ -0,0 +1,156 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::ConversionFailure;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// Defines an operator node: an enum whose variants map one to one to the spellings the grammar
+/// accepts for `$rule`.
+macro_rules! operator {
+    ($name:ident, $rule:ident, { $($variant:ident => $text:literal),+ $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $text),+
+                }
+            }
+        }
+
+        impl FromPest<'_> for $name {
+            type Rule = Rule;
+            type FatalError = ConversionFailure;
+
+            fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+                let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+                if current_rule.as_rule() != Rule::$rule {
+                    return Err(ConversionError::NoMatch);
+                }
+                let operator = match current_rule.as_str() {
+                    $($text => $name::$variant,)+
+                    _ => return Err(ConversionError::Malformed(ConversionFailure::at(&current_rule, stringify!($name)))),
+                };
+                pest.next();
+
+                Ok(operator)
+            }
+        }
+
+        impl PrintAst for $name {
+            fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+                String::from(self.as_str())
+            }
+        }
+
+        impl VisitNodes for $name {
+            fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+        }
+    };
+}
+
+operator!(AssignmentOperator, AssignmentOperator, {
+    Assign => "=",
+    AddAssign => "+=",
+    SubAssign => "-=",
+    PowAssign => "**=",
+    MulAssign => "*=",
+    DivAssign => "/=",
+    IntDivAssign => "\\=",
+    ModAssign => "%=",
+    AndAssign => "&=",
+    OrAssign => "|=",
+    XorAssign => "^=",
+    ShiftLeftAssign => "<<=",
+    UnsignedShiftRightAssign => ">>>=",
+    ShiftRightAssign => ">>=",
+});
+
+operator!(LogicalOrOperator, LogicalOR, {
+    PipePipe => "||",
+    Or => "or",
+});
+
+operator!(LogicalXorOperator, LogicalXOR, {
+    CaretCaret => "^^",
+    Xor => "xor",
+});
+
+operator!(LogicalAndOperator, LogicalAND, {
+    AmpAmp => "&&",
+    And => "and",
+});
+
+operator!(BooleanOrOperator, BooleanOR, {
+    Pipe => "|",
+});
+
+operator!(BooleanXorOperator, BooleanXOR, {
+    Caret => "^",
+});
+
+operator!(BooleanAndOperator, BooleanAND, {
+    Amp => "&",
+});
+
+operator!(EqualityOperator, EqualityOperator, {
+    StrictEqual => "===",
+    StrictNotEqual => "!==",
+    Equal => "==",
+    NotEqual => "!=",
+});
+
+operator!(RelationalOperator, RelationalOperator, {
+    LessEqual => "<=",
+    GreaterEqual => ">=",
+    Less => "<",
+    Greater => ">",
+    InstanceOf => "instanceof",
+    In => "in",
+    As => "as",
+});
+
+operator!(AdditiveOperator, AdditiveOperator, {
+    Plus => "+",
+    Minus => "-",
+});
+
+operator!(ShiftOperator, ShiftOperator, {
+    ShiftLeft => "<<",
+    UnsignedShiftRight => ">>>",
+    ShiftRight => ">>",
+});
+
+operator!(MultiplicativeOperator, MultiplicativeOperator, {
+    Times => "*",
+    Divide => "/",
+    IntDivide => "\\",
+    Modulo => "%",
+});
+
+operator!(PowerOperator, PowerOperator, {
+    Power => "**",
+});
+
+operator!(UnaryOperator, UnaryOperator, {
+    Minus => "-",
+    Not => "!",
+    BitNot => "~",
+    Reference => "@",
+    TypeOf => "typeof",
+    NotKeyword => "not",
+});
+
+operator!(UpdateOperator, UpdateOperator, {
+    Increment => "++",
+    Decrement => "--",
+});
+
+operator!(Arrow, Arrow, {
+    FatArrow => "=>",
+    ThinArrow => "->",
+});
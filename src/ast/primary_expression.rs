@@ -0,0 +1,269 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::array::Array;
+use crate::ast::class_declaration::ClassKeyword;
+use crate::ast::expression::Expression;
+use crate::ast::identifier::Identifier;
+use crate::ast::interval::Interval;
+use crate::ast::map::Map;
+use crate::ast::new_expression::NewExpression;
+use crate::ast::number::Number;
+use crate::ast::object::Object;
+use crate::ast::set::Set;
+use crate::ast::string::StringLiteral;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrimaryExpression {
+    Identifier(AstNode<Identifier>),
+    This(AstNode<ThisKeyword>),
+    Super(AstNode<SuperKeyword>),
+    /// The class of the current method
+    Class(AstNode<ClassKeyword>),
+    New(AstNode<NewExpression>),
+    Number(AstNode<Number>),
+    String(AstNode<StringLiteral>),
+    Constant(AstNode<Constant>),
+    Boolean(AstNode<Boolean>),
+    Null(AstNode<NullKeyword>),
+    Array(AstNode<Array>),
+    Set(AstNode<Set>),
+    Map(AstNode<Map>),
+    Object(AstNode<Object>),
+    Interval(AstNode<Interval>),
+    Parenthesized(AstNode<ParenthesizedExpression>),
+}
+
+node_from_pest!(PrimaryExpression, PrimaryExpression, enum { Identifier, This, Super, Class, New, Number, String, Constant, Boolean, Null, Array, Set, Map, Object, Interval, Parenthesized });
+
+impl PrintAst for PrimaryExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            PrimaryExpression::Identifier(identifier) => identifier.print_ast(print_properties),
+            PrimaryExpression::This(this_keyword) => this_keyword.print_ast(print_properties),
+            PrimaryExpression::Super(super_keyword) => super_keyword.print_ast(print_properties),
+            PrimaryExpression::Class(class_keyword) => class_keyword.print_ast(print_properties),
+            PrimaryExpression::New(new_expression) => new_expression.print_ast(print_properties),
+            PrimaryExpression::Number(number) => number.print_ast(print_properties),
+            PrimaryExpression::String(string) => string.print_ast(print_properties),
+            PrimaryExpression::Constant(constant) => constant.print_ast(print_properties),
+            PrimaryExpression::Boolean(boolean) => boolean.print_ast(print_properties),
+            PrimaryExpression::Null(null) => null.print_ast(print_properties),
+            PrimaryExpression::Array(array) => array.print_ast(print_properties),
+            PrimaryExpression::Set(set) => set.print_ast(print_properties),
+            PrimaryExpression::Map(map) => map.print_ast(print_properties),
+            PrimaryExpression::Object(object) => object.print_ast(print_properties),
+            PrimaryExpression::Interval(interval) => interval.print_ast(print_properties),
+            PrimaryExpression::Parenthesized(parenthesized) => parenthesized.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for PrimaryExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            PrimaryExpression::Identifier(node) => node.visit_nodes(visitor),
+            PrimaryExpression::This(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Super(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Class(node) => node.visit_nodes(visitor),
+            PrimaryExpression::New(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Number(node) => node.visit_nodes(visitor),
+            PrimaryExpression::String(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Constant(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Boolean(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Null(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Array(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Set(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Map(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Object(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Interval(node) => node.visit_nodes(visitor),
+            PrimaryExpression::Parenthesized(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParenthesizedExpression {
+    pub lparen: AstNode<LParen>,
+    pub expression: AstNode<Expression>,
+    pub rparen: AstNode<RParen>,
+}
+
+node_from_pest!(ParenthesizedExpression, ParenthesizedExpression, { lparen, expression, rparen });
+
+impl PrintAst for ParenthesizedExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lparen.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result.push_str(&self.rparen.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ParenthesizedExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lparen.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Boolean {
+    True(AstNode<TrueKeyword>),
+    False(AstNode<FalseKeyword>),
+}
+
+node_from_pest!(Boolean, Boolean, enum { True, False });
+
+impl PrintAst for Boolean {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            Boolean::True(true_keyword) => true_keyword.print_ast(print_properties),
+            Boolean::False(false_keyword) => false_keyword.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Boolean {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Boolean::True(node) => node.visit_nodes(visitor),
+            Boolean::False(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrueKeyword;
+
+node_from_pest!(TrueKeyword, True);
+
+impl PrintAst for TrueKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("true")
+    }
+}
+
+impl VisitNodes for TrueKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FalseKeyword;
+
+node_from_pest!(FalseKeyword, False);
+
+impl PrintAst for FalseKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("false")
+    }
+}
+
+impl VisitNodes for FalseKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThisKeyword;
+
+node_from_pest!(ThisKeyword, This);
+
+impl PrintAst for ThisKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("this")
+    }
+}
+
+impl VisitNodes for ThisKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SuperKeyword;
+
+node_from_pest!(SuperKeyword, Super);
+
+impl PrintAst for SuperKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("super")
+    }
+}
+
+impl VisitNodes for SuperKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NullKeyword;
+
+node_from_pest!(NullKeyword, Null);
+
+impl PrintAst for NullKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("null")
+    }
+}
+
+impl VisitNodes for NullKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Constant {
+    Infinity(AstNode<Infinity>),
+    Pi(AstNode<Pi>),
+}
+
+node_from_pest!(Constant, Constant, enum { Infinity, Pi });
+
+impl PrintAst for Constant {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            Constant::Infinity(infinity) => infinity.print_ast(print_properties),
+            Constant::Pi(pi) => pi.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Constant {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Constant::Infinity(node) => node.visit_nodes(visitor),
+            Constant::Pi(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Infinity;
+
+node_from_pest!(Infinity, Infinity);
+
+impl PrintAst for Infinity {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("∞")
+    }
+}
+
+impl VisitNodes for Infinity {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pi;
+
+node_from_pest!(Pi, PI);
+
+impl PrintAst for Pi {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("π")
+    }
+}
+
+impl VisitNodes for Pi {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
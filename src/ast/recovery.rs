@@ -0,0 +1,126 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::semi::Semi;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// A statement the recovering parser skipped, up to its `;` if it has one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorStatement {
+    pub tokens: Vec<AstNode<ErrorToken>>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(ErrorStatement, ErrorStatement, { tokens, semi });
+
+impl PrintAst for ErrorStatement {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for token in &self.tokens {
+            result.push_str(&token.print_ast(print_properties));
+        }
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ErrorStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.tokens.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+/// An expression the recovering parser skipped
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorExpression {
+    pub tokens: Vec<AstNode<ErrorToken>>,
+}
+
+node_from_pest!(ErrorExpression, ErrorExpression, { tokens });
+
+impl PrintAst for ErrorExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        for token in &self.tokens {
+            result.push_str(&token.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for ErrorExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.tokens.visit_nodes(visitor);
+    }
+}
+
+/// A word, a symbol, a string or a whole bracketed group, as skipped by the recovering parser
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorToken {
+    pub text: String,
+}
+
+impl FromPest<'_> for ErrorToken {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::ErrorToken {
+            return Err(ConversionError::NoMatch);
+        }
+        pest.next();
+
+        Ok(ErrorToken {
+            text: current_rule.as_str().to_string(),
+        })
+    }
+}
+
+impl PrintAst for ErrorToken {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        self.text.clone()
+    }
+}
+
+impl VisitNodes for ErrorToken {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+/// The statement controlled by an `if`, a loop or an `else` that ends right after it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingStatement;
+
+node_from_pest!(MissingStatement, MissingStatement);
+
+impl PrintAst for MissingStatement {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::new()
+    }
+}
+
+impl VisitNodes for MissingStatement {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+/// An expression expected before a `;`, a closing bracket or the next statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingExpression;
+
+node_from_pest!(MissingExpression, MissingExpression);
+
+impl PrintAst for MissingExpression {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::new()
+    }
+}
+
+impl VisitNodes for MissingExpression {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
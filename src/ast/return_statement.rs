@@ -1,20 +1,19 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::expression::Expression;
 use crate::ast::semi::Semi;
-use crate::ast::statement::Statement;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::ReturnStatement))]
 pub struct ReturnStatement {
     pub return_keyword: AstNode<ReturnKeyword>,
     pub expression: Option<AstNode<Expression>>,
     pub semi: Option<AstNode<Semi>>,
 }
 
+node_from_pest!(ReturnStatement, ReturnStatement, { return_keyword, expression, semi });
+
 impl PrintAst for ReturnStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -29,13 +28,25 @@ impl PrintAst for ReturnStatement {
     }
 }
 
+impl VisitNodes for ReturnStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.return_keyword.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Return))]
 pub struct ReturnKeyword;
 
+node_from_pest!(ReturnKeyword, Return);
+
 impl PrintAst for ReturnKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("return")
     }
+}
+
+impl VisitNodes for ReturnKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -1,14 +1,18 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Semi))]
 pub struct Semi;
 
+node_from_pest!(Semi, Semi);
+
 impl PrintAst for Semi {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from(";")
     }
+}
+
+impl VisitNodes for Semi {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
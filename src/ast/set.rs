@@ -0,0 +1,36 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::array::ArrayElements;
+use crate::ast::type_annotation::{LChev, RChev};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+/// `<a, b, c>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Set {
+    pub lchev: AstNode<LChev>,
+    pub elements: Option<AstNode<ArrayElements>>,
+    pub rchev: AstNode<RChev>,
+}
+
+node_from_pest!(Set, Set, { lchev, elements, rchev });
+
+impl PrintAst for Set {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lchev.print_ast(print_properties));
+        if let Some(elements) = &self.elements {
+            result.push_str(&elements.print_ast(print_properties));
+        }
+        result.push_str(&self.rchev.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for Set {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lchev.visit_nodes(visitor);
+        self.elements.visit_nodes(visitor);
+        self.rchev.visit_nodes(visitor);
+    }
+}
@@ -1,10 +1,18 @@
 use crate::ast::expression::ExpressionStatement;
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
+use crate::ast::conversion::ConversionFailure;
 use crate::ast::break_statement::BreakStatement;
+use crate::ast::class_declaration::ClassDeclaration;
 use crate::ast::do_while_statement::DoWhileStatement;
+use crate::ast::enum_declaration::EnumDeclaration;
+use crate::ast::for_statement::ForStatement;
 use crate::ast::function_declaration::FunctionDeclaration;
+use crate::ast::include_statement::IncludeStatement;
+use crate::ast::interface_declaration::InterfaceDeclaration;
+use crate::ast::recovery::{ErrorStatement, MissingStatement};
 use crate::ast::return_statement::ReturnStatement;
+use crate::ast::switch_statement::SwitchStatement;
 use crate::ast::while_statement::WhileStatement;
 use crate::ast::if_statement::IfStatement;
 use crate::ast::variable_declaration::VariableDeclaration;
@@ -14,6 +22,7 @@ use super::continue_statement::ContinueStatement;
 use super::semi::Semi;
 use crate::lsv4::Rule;
 use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Statement {
@@ -22,12 +31,21 @@ pub enum Statement {
     ReturnStatement(AstNode<ReturnStatement>),
     BreakStatement(AstNode<BreakStatement>),
     ContinueStatement(AstNode<ContinueStatement>),
+    IncludeStatement(AstNode<IncludeStatement>),
     ExpressionStatement(AstNode<ExpressionStatement>),
     WhileStatement(AstNode<WhileStatement>),
+    ForStatement(AstNode<ForStatement>),
     DoWhileStatement(AstNode<DoWhileStatement>),
     IfStatement(AstNode<IfStatement>),
     FunctionDeclaration(AstNode<FunctionDeclaration>),
     VariableDeclaration(AstNode<VariableDeclaration>),
+    ClassDeclaration(AstNode<ClassDeclaration>),
+    EnumDeclaration(AstNode<EnumDeclaration>),
+    InterfaceDeclaration(AstNode<InterfaceDeclaration>),
+    SwitchStatement(AstNode<SwitchStatement>),
+    /// Only built by the recovering parser
+    Error(AstNode<ErrorStatement>),
+    Missing(AstNode<MissingStatement>),
 }
 
 impl PrintAst for Statement {
@@ -38,33 +56,69 @@ impl PrintAst for Statement {
             Statement::ReturnStatement(return_statement) => return_statement.print_ast(print_properties),
             Statement::BreakStatement(break_statement) => break_statement.print_ast(print_properties),
             Statement::ContinueStatement(continue_statement) => continue_statement.print_ast(print_properties),
+            Statement::IncludeStatement(include_statement) => include_statement.print_ast(print_properties),
             Statement::ExpressionStatement(expression_statement) => expression_statement.print_ast(print_properties),
             Statement::WhileStatement(while_statement) => while_statement.print_ast(print_properties),
+            Statement::ForStatement(for_statement) => for_statement.print_ast(print_properties),
             Statement::DoWhileStatement(do_while_statement) => do_while_statement.print_ast(print_properties),
             Statement::IfStatement(if_statement) => if_statement.print_ast(print_properties),
             Statement::FunctionDeclaration(function_declaration) => function_declaration.print_ast(print_properties),
             Statement::VariableDeclaration(variable_declaration) => variable_declaration.print_ast(print_properties),
+            Statement::ClassDeclaration(class_declaration) => class_declaration.print_ast(print_properties),
+            Statement::EnumDeclaration(enum_declaration) => enum_declaration.print_ast(print_properties),
+            Statement::InterfaceDeclaration(interface_declaration) => interface_declaration.print_ast(print_properties),
+            Statement::SwitchStatement(switch_statement) => switch_statement.print_ast(print_properties),
+            Statement::Error(error_statement) => error_statement.print_ast(print_properties),
+            Statement::Missing(missing_statement) => missing_statement.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for Statement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            Statement::EmptyStatement(node) => node.visit_nodes(visitor),
+            Statement::BlockStatement(node) => node.visit_nodes(visitor),
+            Statement::ReturnStatement(node) => node.visit_nodes(visitor),
+            Statement::BreakStatement(node) => node.visit_nodes(visitor),
+            Statement::ContinueStatement(node) => node.visit_nodes(visitor),
+            Statement::IncludeStatement(node) => node.visit_nodes(visitor),
+            Statement::ExpressionStatement(node) => node.visit_nodes(visitor),
+            Statement::WhileStatement(node) => node.visit_nodes(visitor),
+            Statement::ForStatement(node) => node.visit_nodes(visitor),
+            Statement::DoWhileStatement(node) => node.visit_nodes(visitor),
+            Statement::IfStatement(node) => node.visit_nodes(visitor),
+            Statement::FunctionDeclaration(node) => node.visit_nodes(visitor),
+            Statement::VariableDeclaration(node) => node.visit_nodes(visitor),
+            Statement::ClassDeclaration(node) => node.visit_nodes(visitor),
+            Statement::EnumDeclaration(node) => node.visit_nodes(visitor),
+            Statement::InterfaceDeclaration(node) => node.visit_nodes(visitor),
+            Statement::SwitchStatement(node) => node.visit_nodes(visitor),
+            Statement::Error(node) => node.visit_nodes(visitor),
+            Statement::Missing(node) => node.visit_nodes(visitor),
         }
     }
 }
 
 impl FromPest<'_> for Statement {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
-        if current_rule.as_rule() != Rule::Statement {
-            return Err(ConversionError::NoMatch);
+        match current_rule.as_rule() {
+            Rule::Statement => {}
+            // The recovering parser also puts these where a statement is expected
+            Rule::ErrorStatement => return Ok(Statement::Error(AstNode::from_pest(pest)?)),
+            Rule::MissingStatement => return Ok(Statement::Missing(AstNode::from_pest(pest)?)),
+            _ => return Err(ConversionError::NoMatch),
         }
         current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        let mut context = &mut current_rule.clone().into_inner();
+        let mut context = current_rule.clone().into_inner();
 
         let next = find_next_non_comment_or_whitespace(&mut context)?;
 
-        println!("next: {:?}", next.clone().map(|n| n.as_rule()));
-
         if let Some(next) = next {
             Ok(match next.as_rule() {
                 Rule::Semi => {
@@ -82,12 +136,18 @@ impl FromPest<'_> for Statement {
                 Rule::ContinueStatement => {
                     Statement::ContinueStatement(AstNode::from_pest(&mut context)?)
                 },
+                Rule::IncludeStatement => {
+                    Statement::IncludeStatement(AstNode::from_pest(&mut context)?)
+                },
                 Rule::ExpressionStatement => {
                     Statement::ExpressionStatement(AstNode::from_pest(&mut context)?)
                 },
                 Rule::WhileStatement => {
                     Statement::WhileStatement(AstNode::from_pest(&mut context)?)
                 },
+                Rule::ForStatement => {
+                    Statement::ForStatement(AstNode::from_pest(&mut context)?)
+                },
                 Rule::DoWhileStatement => {
                     Statement::DoWhileStatement(AstNode::from_pest(&mut context)?)
                 },
@@ -100,13 +160,25 @@ impl FromPest<'_> for Statement {
                 Rule::VariableDeclaration => {
                     Statement::VariableDeclaration(AstNode::from_pest(&mut context)?)
                 },
-                rule => {
-                    println!("Unexpected rule: {:?}", rule);
-                    return Err(ConversionError::NoMatch);
+                Rule::ClassDeclaration => {
+                    Statement::ClassDeclaration(AstNode::from_pest(&mut context)?)
+                },
+                Rule::EnumDeclaration => {
+                    Statement::EnumDeclaration(AstNode::from_pest(&mut context)?)
+                },
+                Rule::InterfaceDeclaration => {
+                    Statement::InterfaceDeclaration(AstNode::from_pest(&mut context)?)
+                },
+                Rule::SwitchStatement => {
+                    Statement::SwitchStatement(AstNode::from_pest(&mut context)?)
+                },
+                Rule::ErrorStatement => {
+                    Statement::Error(AstNode::from_pest(&mut context)?)
                 },
+                _ => return Err(ConversionError::Malformed(ConversionFailure::at(&next, "Statement"))),
             })
         } else {
-            Err(ConversionError::NoMatch)
+            Err(ConversionError::Malformed(ConversionFailure::at(&current_rule, "Statement")))
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,136 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::ConversionFailure;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+/// A quoted string
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StringLiteral {
+    /// The literal as it appears in the source, quotes and escape sequences included
+    pub raw: String,
+    /// The content of the string once the escape sequences are decoded. Invalid escape sequences
+    /// are kept as they are written.
+    pub value: String,
+}
+
+impl FromPest<'_> for StringLiteral {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::String {
+            return Err(ConversionError::NoMatch);
+        }
+        pest.next();
+
+        let raw = current_rule.as_str().to_string();
+        let (value, _) = decode_string(&raw);
+
+        Ok(StringLiteral {
+            raw,
+            value,
+        })
+    }
+}
+
+impl PrintAst for StringLiteral {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        self.raw.clone()
+    }
+}
+
+impl VisitNodes for StringLiteral {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+/// An escape sequence LeekScript doesn't know, as a byte range in the raw literal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidEscape {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Decodes the escape sequences of a raw string literal, quotes included.
+///
+/// The known escapes are `\n`, `\t`, `\r`, `\b`, `\f`, `\\`, `\'`, `\"` and `\uXXXX`, where a
+/// surrogate pair can be written as two consecutive `\uXXXX`.
+pub fn decode_string(raw: &str) -> (String, Vec<InvalidEscape>) {
+    let mut value = String::new();
+    let mut invalid_escapes = Vec::new();
+
+    // Skip the quotes, offsets stay relative to the raw literal
+    let inner = raw.get(1..raw.len().saturating_sub(1)).unwrap_or("");
+    let mut chars = inner.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let start = index + 1;
+        let Some((_, escaped)) = chars.next() else {
+            break;
+        };
+        let decoded = match escaped {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            'b' => Some('\u{8}'),
+            'f' => Some('\u{c}'),
+            '\\' | '\'' | '"' => Some(escaped),
+            'u' => decode_unicode_escape(inner, &mut chars),
+            _ => None,
+        };
+        match decoded {
+            Some(decoded) => value.push(decoded),
+            None => {
+                let end = chars.peek().map_or(inner.len(), |(index, _)| *index) + 1;
+                value.push_str(&raw[start..end]);
+                invalid_escapes.push(InvalidEscape { start, end });
+            }
+        }
+    }
+
+    (value, invalid_escapes)
+}
+
+/// Decodes the digits of a `\uXXXX` escape, and the low surrogate that follows if it is a high
+/// surrogate. `chars` is right after the `u`. On failure, the hex digits read are consumed so
+/// they are part of the invalid escape.
+fn decode_unicode_escape(inner: &str, chars: &mut std::iter::Peekable<std::str::CharIndices>) -> Option<char> {
+    let high = read_hex_digits(chars)?;
+    if let Some(c) = char::from_u32(high) {
+        return Some(c);
+    }
+    if !(0xD800..0xDC00).contains(&high) {
+        return None;
+    }
+
+    // A high surrogate must be followed by `\u` and a low surrogate
+    let rest = chars.peek().map_or("", |(index, _)| &inner[*index..]);
+    if !rest.starts_with("\\u") {
+        return None;
+    }
+    let mut lookahead = chars.clone();
+    lookahead.next();
+    lookahead.next();
+    let low = read_hex_digits(&mut lookahead)?;
+    if !(0xDC00..0xE000).contains(&low) {
+        return None;
+    }
+    *chars = lookahead;
+    char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+}
+
+/// Reads exactly four hex digits, consuming the leading hex digits even when there are fewer
+fn read_hex_digits(chars: &mut std::iter::Peekable<std::str::CharIndices>) -> Option<u32> {
+    let mut code = 0;
+    for _ in 0..4 {
+        let digit = chars.peek().and_then(|(_, c)| c.to_digit(16))?;
+        chars.next();
+        code = code * 16 + digit;
+    }
+    Some(code)
+}
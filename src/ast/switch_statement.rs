@@ -0,0 +1,189 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::block_statement::{Lbrace, Rbrace};
+use crate::ast::expression::Expression;
+use crate::ast::semi::Semi;
+use crate::ast::statement::Statement;
+use crate::ast::ternary_expression::Colon;
+use crate::ast::while_statement::{LParen, RParen};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SwitchStatement {
+    pub switch_keyword: AstNode<SwitchKeyword>,
+    pub lparen: AstNode<LParen>,
+    pub expression: AstNode<Expression>,
+    pub rparen: AstNode<RParen>,
+    pub lbrace: AstNode<Lbrace>,
+    pub clauses: Vec<AstNode<SwitchClause>>,
+    pub rbrace: AstNode<Rbrace>,
+    pub semi: Option<AstNode<Semi>>,
+}
+
+node_from_pest!(SwitchStatement, SwitchStatement, { switch_keyword, lparen, expression, rparen, lbrace, clauses, rbrace, semi });
+
+impl PrintAst for SwitchStatement {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.switch_keyword.print_ast(print_properties));
+        result.push_str(&self.lparen.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result.push_str(&self.rparen.print_ast(print_properties));
+        result.push_str(&self.lbrace.print_ast(print_properties));
+        for clause in &self.clauses {
+            result.push_str(&clause.print_ast(print_properties));
+        }
+        result.push_str(&self.rbrace.print_ast(print_properties));
+        if let Some(semi) = &self.semi {
+            result.push_str(&semi.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for SwitchStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.switch_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.lbrace.visit_nodes(visitor);
+        self.clauses.visit_nodes(visitor);
+        self.rbrace.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SwitchClause {
+    Case(AstNode<CaseClause>),
+    Default(AstNode<DefaultClause>),
+}
+
+node_from_pest!(SwitchClause, SwitchClause, enum { Case, Default });
+
+impl PrintAst for SwitchClause {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            SwitchClause::Case(case_clause) => case_clause.print_ast(print_properties),
+            SwitchClause::Default(default_clause) => default_clause.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for SwitchClause {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            SwitchClause::Case(node) => node.visit_nodes(visitor),
+            SwitchClause::Default(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+/// `case value: statements`. Without a `break`, the execution falls through to the next clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CaseClause {
+    pub case_keyword: AstNode<CaseKeyword>,
+    pub expression: AstNode<Expression>,
+    pub colon: AstNode<Colon>,
+    pub statements: Vec<AstNode<Statement>>,
+}
+
+node_from_pest!(CaseClause, CaseClause, { case_keyword, expression, colon, statements });
+
+impl PrintAst for CaseClause {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.case_keyword.print_ast(print_properties));
+        result.push_str(&self.expression.print_ast(print_properties));
+        result.push_str(&self.colon.print_ast(print_properties));
+        for statement in &self.statements {
+            result.push_str(&statement.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for CaseClause {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.case_keyword.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+        self.statements.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DefaultClause {
+    pub default_keyword: AstNode<DefaultKeyword>,
+    pub colon: AstNode<Colon>,
+    pub statements: Vec<AstNode<Statement>>,
+}
+
+node_from_pest!(DefaultClause, DefaultClause, { default_keyword, colon, statements });
+
+impl PrintAst for DefaultClause {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.default_keyword.print_ast(print_properties));
+        result.push_str(&self.colon.print_ast(print_properties));
+        for statement in &self.statements {
+            result.push_str(&statement.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for DefaultClause {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.default_keyword.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+        self.statements.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SwitchKeyword;
+
+node_from_pest!(SwitchKeyword, Switch);
+
+impl PrintAst for SwitchKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("switch")
+    }
+}
+
+impl VisitNodes for SwitchKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CaseKeyword;
+
+node_from_pest!(CaseKeyword, Case);
+
+impl PrintAst for CaseKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("case")
+    }
+}
+
+impl VisitNodes for CaseKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DefaultKeyword;
+
+node_from_pest!(DefaultKeyword, Default);
+
+impl PrintAst for DefaultKeyword {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("default")
+    }
+}
+
+impl VisitNodes for DefaultKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
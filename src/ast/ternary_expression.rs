@@ -0,0 +1,124 @@
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::binary_expression::LogicalOrExpression;
+use crate::ast::expression::Expression;
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+use crate::Rule;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TernaryExpression {
+    pub condition: AstNode<LogicalOrExpression>,
+    pub branches: Vec<TernaryBranch>,
+}
+
+impl FromPest<'_> for TernaryExpression {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::TernaryExpression {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let condition = required(&current_rule, &mut context)?;
+        let mut branches = Vec::new();
+        while let Some(question_mark) = Option::from_pest(&mut context)? {
+            branches.push(TernaryBranch {
+                question_mark,
+                consequent: required(&current_rule, &mut context)?,
+                colon: required(&current_rule, &mut context)?,
+                alternative: required(&current_rule, &mut context)?,
+            });
+        }
+
+        Ok(TernaryExpression {
+            condition,
+            branches,
+        })
+    }
+}
+
+impl PrintAst for TernaryExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.condition.print_ast(print_properties));
+        for branch in &self.branches {
+            result.push_str(&branch.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for TernaryExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.condition.visit_nodes(visitor);
+        self.branches.visit_nodes(visitor);
+    }
+}
+
+/// The `? consequent : alternative` part of a ternary expression. It is not a rule of its own in
+/// the grammar, so it carries no trivia by itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TernaryBranch {
+    pub question_mark: AstNode<QuestionMark>,
+    pub consequent: AstNode<Expression>,
+    pub colon: AstNode<Colon>,
+    pub alternative: AstNode<Expression>,
+}
+
+impl PrintAst for TernaryBranch {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.question_mark.print_ast(print_properties));
+        result.push_str(&self.consequent.print_ast(print_properties));
+        result.push_str(&self.colon.print_ast(print_properties));
+        result.push_str(&self.alternative.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for TernaryBranch {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.question_mark.visit_nodes(visitor);
+        self.consequent.visit_nodes(visitor);
+        self.colon.visit_nodes(visitor);
+        self.alternative.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuestionMark;
+
+node_from_pest!(QuestionMark, QuestionMark);
+
+impl PrintAst for QuestionMark {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("?")
+    }
+}
+
+impl VisitNodes for QuestionMark {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Colon;
+
+node_from_pest!(Colon, Colon);
+
+impl PrintAst for Colon {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from(":")
+    }
+}
+
+impl VisitNodes for Colon {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
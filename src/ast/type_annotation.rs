@@ -1,32 +1,254 @@
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
+use crate::ast::AstNode;
+use crate::ast::function_declaration::Comma;
+use crate::ast::identifier::Identifier;
+use crate::ast::operators::Arrow;
+use crate::ast::ternary_expression::QuestionMark;
 use crate::lsv4::Rule;
-use crate::utils::PrintAst;
+use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 
+/// A type, such as `integer`, `Array<real>?`, `integer | string` or `Function<integer => void>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TypeAnnotation;
+pub struct TypeAnnotation {
+    /// The members of the union, a single one for a plain type
+    pub base_types: Vec<AstNode<BaseType>>,
+    pub pipes: Vec<AstNode<Pipe>>,
+    /// Present when the type is nullable
+    pub question_mark: Option<AstNode<QuestionMark>>,
+}
+
+impl TypeAnnotation {
+    pub fn is_nullable(&self) -> bool {
+        self.question_mark.is_some()
+    }
+}
 
 impl PrintAst for TypeAnnotation {
-    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
-        String::from("TYPE ANNOTATION")
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(base_type) = self.base_types.first() {
+            result.push_str(&base_type.print_ast(print_properties));
+        }
+        for (pipe, base_type) in self.pipes.iter().zip(self.base_types.iter().skip(1)) {
+            result.push_str(&pipe.print_ast(print_properties));
+            result.push_str(&base_type.print_ast(print_properties));
+        }
+        if let Some(question_mark) = &self.question_mark {
+            result.push_str(&question_mark.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for TypeAnnotation {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.base_types.visit_nodes(visitor);
+        self.pipes.visit_nodes(visitor);
+        self.question_mark.visit_nodes(visitor);
     }
 }
 
 impl FromPest<'_> for TypeAnnotation {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<'_, Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
-        if pest.peek().is_none() {
-            return Err(ConversionError::Extraneous { current_node: "Type annotation" })
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::Type {
+            return Err(ConversionError::NoMatch);
+        }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
+
+        let mut context = current_rule.clone().into_inner();
+
+        let mut base_types = vec![required(&current_rule, &mut context)?];
+        let mut pipes = Vec::new();
+        while let Some(pipe) = Option::from_pest(&mut context)? {
+            pipes.push(pipe);
+            base_types.push(required(&current_rule, &mut context)?);
         }
-        if pest.peek().unwrap().as_rule() != Rule::Type {
+        let question_mark = Option::from_pest(&mut context)?;
+
+        Ok(TypeAnnotation {
+            base_types,
+            pipes,
+            question_mark,
+        })
+    }
+}
+
+/// A named type with its optional generic arguments, such as `Array<integer>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BaseType {
+    pub identifier: AstNode<Identifier>,
+    pub sub_type: Option<AstNode<SubType>>,
+}
+
+node_from_pest!(BaseType, BaseType, { identifier, sub_type });
+
+impl PrintAst for BaseType {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.identifier.print_ast(print_properties));
+        if let Some(sub_type) = &self.sub_type {
+            result.push_str(&sub_type.print_ast(print_properties));
+        }
+        result
+    }
+}
+
+impl VisitNodes for BaseType {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.identifier.visit_nodes(visitor);
+        self.sub_type.visit_nodes(visitor);
+    }
+}
+
+/// The `<...>` part of a type: the generic arguments and, for functions, the return type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubType {
+    pub lchev: AstNode<LChev>,
+    pub types: Vec<AstNode<TypeAnnotation>>,
+    pub commas: Vec<AstNode<Comma>>,
+    pub return_type: Option<AstNode<ReturnType>>,
+    pub rchev: AstNode<RChev>,
+}
+
+impl FromPest<'_> for SubType {
+    type Rule = Rule;
+    type FatalError = ConversionFailure;
+
+    fn from_pest(pest: &mut Pairs<'_, Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::SubType {
             return Err(ConversionError::NoMatch);
         }
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        // TODO - actually parse the type
-        pest.next();
+        let mut context = current_rule.clone().into_inner();
+
+        let lchev = required(&current_rule, &mut context)?;
+        let mut types = Vec::new();
+        let mut commas = Vec::new();
+        while let Some(next) = find_next_non_comment_or_whitespace(&mut context)? {
+            match next.as_rule() {
+                Rule::Type => types.push(AstNode::from_pest(&mut context)?),
+                Rule::Comma => commas.push(AstNode::from_pest(&mut context)?),
+                _ => break,
+            };
+        }
+        let return_type = Option::from_pest(&mut context)?;
+        let rchev = required(&current_rule, &mut context)?;
 
-        Ok(TypeAnnotation)
+        Ok(SubType {
+            lchev,
+            types,
+            commas,
+            return_type,
+            rchev,
+        })
     }
-}
\ No newline at end of file
+}
+
+impl PrintAst for SubType {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.lchev.print_ast(print_properties));
+        for (type_annotation, comma) in self.types.iter().zip(self.commas.iter()) {
+            result.push_str(&type_annotation.print_ast(print_properties));
+            result.push_str(&comma.print_ast(print_properties));
+        }
+        // Add the last type, which doesn't have a comma after it
+        if let Some(type_annotation) = self.types.last() {
+            result.push_str(&type_annotation.print_ast(print_properties));
+        }
+        if let Some(return_type) = &self.return_type {
+            result.push_str(&return_type.print_ast(print_properties));
+        }
+        result.push_str(&self.rchev.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for SubType {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.lchev.visit_nodes(visitor);
+        self.types.visit_nodes(visitor);
+        self.commas.visit_nodes(visitor);
+        self.return_type.visit_nodes(visitor);
+        self.rchev.visit_nodes(visitor);
+    }
+}
+
+/// `=> type`, the return type of a function type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReturnType {
+    pub arrow: AstNode<Arrow>,
+    pub type_annotation: AstNode<TypeAnnotation>,
+}
+
+node_from_pest!(ReturnType, ReturnType, { arrow, type_annotation });
+
+impl PrintAst for ReturnType {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.arrow.print_ast(print_properties));
+        result.push_str(&self.type_annotation.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for ReturnType {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.arrow.visit_nodes(visitor);
+        self.type_annotation.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pipe;
+
+node_from_pest!(Pipe, Pipe);
+
+impl PrintAst for Pipe {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("|")
+    }
+}
+
+impl VisitNodes for Pipe {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LChev;
+
+node_from_pest!(LChev, LChev);
+
+impl PrintAst for LChev {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from("<")
+    }
+}
+
+impl VisitNodes for LChev {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RChev;
+
+node_from_pest!(RChev, RChev);
+
+impl PrintAst for RChev {
+    fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
+        String::from(">")
+    }
+}
+
+impl VisitNodes for RChev {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
@@ -0,0 +1,109 @@
+use crate::ast::conversion::node_from_pest;
+use crate::ast::AstNode;
+use crate::ast::member_expression::MemberExpression;
+use crate::ast::operators::{UnaryOperator, UpdateOperator};
+use crate::utils::PrintAst;
+use crate::ast::{NodeVisitor, VisitNodes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UpdateExpression {
+    PreUpdate(AstNode<PreUpdateExpression>),
+    PostUpdate(AstNode<PostUpdateExpression>),
+    Unary(AstNode<UnaryExpression>),
+}
+
+node_from_pest!(UpdateExpression, UpdateExpression, enum { PreUpdate, PostUpdate, Unary });
+
+impl PrintAst for UpdateExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        match self {
+            UpdateExpression::PreUpdate(pre_update) => pre_update.print_ast(print_properties),
+            UpdateExpression::PostUpdate(post_update) => post_update.print_ast(print_properties),
+            UpdateExpression::Unary(unary) => unary.print_ast(print_properties),
+        }
+    }
+}
+
+impl VisitNodes for UpdateExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            UpdateExpression::PreUpdate(node) => node.visit_nodes(visitor),
+            UpdateExpression::PostUpdate(node) => node.visit_nodes(visitor),
+            UpdateExpression::Unary(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PreUpdateExpression {
+    pub operator: AstNode<UpdateOperator>,
+    pub member_expression: AstNode<MemberExpression>,
+}
+
+node_from_pest!(PreUpdateExpression, PreUpdateExpression, { operator, member_expression });
+
+impl PrintAst for PreUpdateExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.operator.print_ast(print_properties));
+        result.push_str(&self.member_expression.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for PreUpdateExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.operator.visit_nodes(visitor);
+        self.member_expression.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PostUpdateExpression {
+    pub member_expression: AstNode<MemberExpression>,
+    pub operator: AstNode<UpdateOperator>,
+}
+
+node_from_pest!(PostUpdateExpression, PostUpdateExpression, { member_expression, operator });
+
+impl PrintAst for PostUpdateExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        result.push_str(&self.member_expression.print_ast(print_properties));
+        result.push_str(&self.operator.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for PostUpdateExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.member_expression.visit_nodes(visitor);
+        self.operator.visit_nodes(visitor);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnaryExpression {
+    pub operator: Option<AstNode<UnaryOperator>>,
+    pub member_expression: AstNode<MemberExpression>,
+}
+
+node_from_pest!(UnaryExpression, UnaryExpression, { operator, member_expression });
+
+impl PrintAst for UnaryExpression {
+    fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
+        let mut result = String::new();
+        if let Some(operator) = &self.operator {
+            result.push_str(&operator.print_ast(print_properties));
+        }
+        result.push_str(&self.member_expression.print_ast(print_properties));
+        result
+    }
+}
+
+impl VisitNodes for UnaryExpression {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.operator.visit_nodes(visitor);
+        self.member_expression.visit_nodes(visitor);
+    }
+}
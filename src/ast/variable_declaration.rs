@@ -1,13 +1,13 @@
-use from_pest::{ConversionError, FromPest, Void};
+use from_pest::{ConversionError, FromPest};
 use pest::iterators::Pairs;
-use pest_ast::FromPest;
+use crate::ast::conversion::{node_from_pest, required, ConversionFailure};
 use crate::ast::AstNode;
-use crate::ast::continue_statement::ContinueKeyword;
 use crate::ast::expression::Expression;
 use crate::ast::identifier::Identifier;
 use crate::ast::semi::Semi;
 use crate::ast::type_annotation::TypeAnnotation;
 use crate::utils::{find_next_non_comment_or_whitespace, PrintAst};
+use crate::ast::{NodeVisitor, VisitNodes};
 use crate::Rule;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,19 +21,22 @@ pub struct VariableDeclaration {
 
 impl FromPest<'_> for VariableDeclaration {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<'_, Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
-        if pest.peek().unwrap().as_rule() != Rule::VariableDeclaration {
+        let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
+        if current_rule.as_rule() != Rule::VariableDeclaration {
             return Err(ConversionError::NoMatch);
         }
-        let mut context = pest.next().unwrap().into_inner();
+        current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        let var_decl_keyword = AstNode::from_pest(&mut context)?;
-        let identifier = AstNode::from_pest(&mut context)?;
-        let equal = AstNode::from_pest(&mut context).ok();
-        let expression = AstNode::from_pest(&mut context).ok();
-        let semi = AstNode::from_pest(&mut context).ok();
+        let mut context = current_rule.clone().into_inner();
+
+        let var_decl_keyword = required(&current_rule, &mut context)?;
+        let identifier = required(&current_rule, &mut context)?;
+        let equal = Option::from_pest(&mut context)?;
+        let expression = Option::from_pest(&mut context)?;
+        let semi = Option::from_pest(&mut context)?;
 
         Ok(VariableDeclaration {
             var_decl_keyword,
@@ -63,6 +66,16 @@ impl PrintAst for VariableDeclaration {
     }
 }
 
+impl VisitNodes for VariableDeclaration {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.var_decl_keyword.visit_nodes(visitor);
+        self.identifier.visit_nodes(visitor);
+        self.equal.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.semi.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum VarDeclKeyword {
     VarDec(AstNode<AstNode<VarDecKeyword>>),
@@ -71,7 +84,7 @@ pub enum VarDeclKeyword {
 
 impl FromPest<'_> for VarDeclKeyword {
     type Rule = Rule;
-    type FatalError = Void;
+    type FatalError = ConversionFailure;
 
     fn from_pest(pest: &mut Pairs<'_, Self::Rule>) -> Result<Self, ConversionError<Self::FatalError>> {
         let mut current_rule = pest.peek().ok_or(ConversionError::NoMatch)?;
@@ -80,19 +93,19 @@ impl FromPest<'_> for VarDeclKeyword {
         }
         current_rule = pest.next().ok_or(ConversionError::NoMatch)?;
 
-        let mut context = &mut current_rule.clone().into_inner();
+        let mut context = current_rule.clone().into_inner();
 
-        let next = find_next_non_comment_or_whitespace(&mut context)?.unwrap().as_rule();
+        let next = find_next_non_comment_or_whitespace(&mut context)?.map(|next| next.as_rule());
         let var_decl_keyword = match next {
-            Rule::VarDec => {
+            Some(Rule::VarDec) => {
                 VarDeclKeyword::VarDec(AstNode::from_pest(&mut context)?)
             },
-            Rule::Global => {
-                let global_keyword = AstNode::from_pest(&mut context)?;
-                let type_annotation = AstNode::from_pest(&mut context).ok();
+            Some(Rule::Global) => {
+                let global_keyword = required(&current_rule, &mut context)?;
+                let type_annotation = Option::from_pest(&mut context)?;
                 VarDeclKeyword::Global(global_keyword, type_annotation)
             }
-            _ => return Err(ConversionError::NoMatch),
+            _ => return Err(ConversionError::Malformed(ConversionFailure::missing(&current_rule, &context, "VarDeclKeyword"))),
         };
 
         Ok(var_decl_keyword)
@@ -115,14 +128,26 @@ impl PrintAst for VarDeclKeyword {
     }
 }
 
+impl VisitNodes for VarDeclKeyword {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            VarDeclKeyword::VarDec(node) => node.visit_nodes(visitor),
+            VarDeclKeyword::Global(global_keyword, type_annotation) => {
+                global_keyword.visit_nodes(visitor);
+                type_annotation.visit_nodes(visitor);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::VarDec))]
 pub enum VarDecKeyword {
     Var(AstNode<VarKeyword>),
     Type(AstNode<TypeAnnotation>),
 }
 
+node_from_pest!(VarDecKeyword, VarDec, enum { Var, Type });
+
 impl PrintAst for VarDecKeyword {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         match self {
@@ -132,35 +157,56 @@ impl PrintAst for VarDecKeyword {
     }
 }
 
+impl VisitNodes for VarDecKeyword {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        match self {
+            VarDecKeyword::Var(node) => node.visit_nodes(visitor),
+            VarDecKeyword::Type(node) => node.visit_nodes(visitor),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Var))]
 pub struct VarKeyword;
 
+node_from_pest!(VarKeyword, Var);
+
 impl PrintAst for VarKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("var")
     }
 }
 
+impl VisitNodes for VarKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Global))]
 pub struct GlobalKeyword;
 
+node_from_pest!(GlobalKeyword, Global);
+
 impl PrintAst for GlobalKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("global")
     }
 }
 
+impl VisitNodes for GlobalKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::Eq))]
 pub struct Equal;
 
+node_from_pest!(Equal, Eq);
+
 impl PrintAst for Equal {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("=")
     }
+}
+
+impl VisitNodes for Equal {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -1,14 +1,11 @@
-use pest_ast::FromPest;
+use crate::ast::conversion::node_from_pest;
 use crate::ast::AstNode;
 use crate::ast::expression::Expression;
-use crate::ast::semi::Semi;
 use crate::ast::statement::Statement;
 use crate::utils::PrintAst;
-use crate::Rule;
+use crate::ast::{NodeVisitor, VisitNodes};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::WhileStatement))]
 pub struct WhileStatement {
     pub while_keyword: AstNode<WhileKeyword>,
     pub lparen: AstNode<LParen>,
@@ -17,6 +14,8 @@ pub struct WhileStatement {
     pub statement: AstNode<Statement>,
 }
 
+node_from_pest!(WhileStatement, WhileStatement, { while_keyword, lparen, expression, rparen, statement });
+
 impl PrintAst for WhileStatement {
     fn print_ast(&self, print_properties: crate::utils::PrintProperties) -> String {
         let mut result = String::new();
@@ -29,35 +28,57 @@ impl PrintAst for WhileStatement {
     }
 }
 
+impl VisitNodes for WhileStatement {
+    fn visit_nodes(&mut self, visitor: &mut NodeVisitor<'_>) {
+        self.while_keyword.visit_nodes(visitor);
+        self.lparen.visit_nodes(visitor);
+        self.expression.visit_nodes(visitor);
+        self.rparen.visit_nodes(visitor);
+        self.statement.visit_nodes(visitor);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::While))]
 pub struct WhileKeyword;
 
+node_from_pest!(WhileKeyword, While);
+
 impl PrintAst for WhileKeyword {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("while")
     }
 }
 
+impl VisitNodes for WhileKeyword {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::LParen))]
 pub struct LParen;
 
+node_from_pest!(LParen, LParen);
+
 impl PrintAst for LParen {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from("(")
     }
 }
 
+impl VisitNodes for LParen {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[derive(FromPest)]
-#[pest_ast(rule(Rule::RParen))]
 pub struct RParen;
 
+node_from_pest!(RParen, RParen);
+
 impl PrintAst for RParen {
     fn print_ast(&self, _print_properties: crate::utils::PrintProperties) -> String {
         String::from(")")
     }
+}
+
+impl VisitNodes for RParen {
+    fn visit_nodes(&mut self, _visitor: &mut NodeVisitor<'_>) {}
 }
\ No newline at end of file
@@ -0,0 +1,179 @@
+pub mod output;
+mod render;
+
+use std::fmt;
+use from_pest::ConversionError;
+use pest::error::{Error, ErrorVariant, InputLocation};
+use crate::ast::conversion::ConversionFailure;
+use crate::lsv4::Rule;
+
+/// The codes identifying each kind of diagnostic. They never change meaning, so that tools can
+/// rely on them.
+pub mod codes {
+    // Syntax
+    /// The source doesn't match the grammar
+    pub const SYNTAX_ERROR: &str = "E0001";
+    /// A statement the recovering parser skipped
+    pub const INVALID_STATEMENT: &str = "E0002";
+    /// An expression the recovering parser skipped
+    pub const INVALID_EXPRESSION: &str = "E0003";
+    pub const MISSING_STATEMENT: &str = "E0004";
+    pub const MISSING_EXPRESSION: &str = "E0005";
+    /// The source matches the grammar but the syntax tree can't represent it
+    pub const CONVERSION_FAILURE: &str = "E0006";
+    /// The source is nested deeper than a syntax tree is built for
+    pub const TOO_DEEP: &str = "E0007";
+    /// pest gave up on the source after the call limit the program set
+    pub const TOO_COMPLEX: &str = "E0008";
+    /// The source is longer than an arena tree can hold
+    pub const TOO_LONG: &str = "E0009";
+
+    // Literals
+    pub const INVALID_ESCAPE: &str = "E0100";
+    pub const MISPLACED_SEPARATOR: &str = "E0101";
+    pub const MISSING_DIGITS: &str = "E0102";
+    pub const INVALID_DIGIT: &str = "E0103";
+    pub const MALFORMED_NUMBER: &str = "E0104";
+    pub const INTEGER_OVERFLOW: &str = "E0105";
+    pub const REAL_OVERFLOW: &str = "E0106";
+
+    // Declarations and expressions
+    pub const DUPLICATE_RETURN_TYPE: &str = "E0200";
+    pub const OUTSIDE_OF_CLASS: &str = "E0201";
+    pub const UNAVAILABLE_IN_VERSION: &str = "E0202";
+    /// A keyword of the selected version used as a name, which the older versions allow
+    pub const RESERVED_NAME: &str = "E0203";
+
+    // Projects
+    pub const INCLUDE_OUTSIDE_OF_PROJECT: &str = "E0300";
+    pub const INCLUDE_CYCLE: &str = "E0301";
+    pub const INCLUDE_NOT_FOUND: &str = "E0302";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        })
+    }
+}
+
+/// A problem found in a source
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// One of the [`codes`]
+    pub code: &'static str,
+    pub message: String,
+    /// Byte range of the problem in the source, which the primary label points at
+    pub start: usize,
+    pub end: usize,
+    /// Text of the primary label
+    pub label: Option<String>,
+    /// Other places of the source related to the problem
+    pub secondary_labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub fixes: Vec<Fix>,
+}
+
+/// A byte range of the source with a message
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub start: usize,
+    pub end: usize,
+    pub message: String,
+}
+
+/// A suggestion that solves the problem by replacing a byte range of the source
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fix {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>, start: usize, end: usize) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            start,
+            end,
+            label: None,
+            secondary_labels: Vec::new(),
+            notes: Vec::new(),
+            fixes: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_secondary_label(mut self, start: usize, end: usize, message: impl Into<String>) -> Self {
+        self.secondary_labels.push(Label { start, end, message: message.into() });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_fix(mut self, message: impl Into<String>, start: usize, end: usize, replacement: impl Into<String>) -> Self {
+        self.fixes.push(Fix {
+            message: message.into(),
+            start,
+            end,
+            replacement: replacement.into(),
+        });
+        self
+    }
+}
+
+impl From<Error<Rule>> for Diagnostic {
+    fn from(error: Error<Rule>) -> Self {
+        let (start, end) = match error.location {
+            InputLocation::Pos(position) => (position, position),
+            InputLocation::Span(span) => span,
+        };
+        // The limits of pest, the call limit being the one the program sets with `pest::set_call_limit`
+        match &error.variant {
+            ErrorVariant::CustomError { message } if message == "stack limit reached" => {
+                Diagnostic::error(codes::TOO_DEEP, "The code is nested too deeply to be parsed", start, end)
+            }
+            ErrorVariant::CustomError { message } if message == "call limit reached" => {
+                Diagnostic::error(codes::TOO_COMPLEX, "The code takes too long to parse", start, end)
+                    .with_note("Deeply nested parentheses, brackets and calls are slow to parse")
+            }
+            _ => Diagnostic::error(codes::SYNTAX_ERROR, error.variant.message(), start, end),
+        }
+    }
+}
+
+impl From<ConversionError<ConversionFailure>> for Diagnostic {
+    fn from(error: ConversionError<ConversionFailure>) -> Self {
+        let diagnostic = match error {
+            ConversionError::Malformed(failure) => Diagnostic::error(
+                codes::CONVERSION_FAILURE,
+                format!("Could not build the syntax tree: expected a `{}` node, found `{:?}`", failure.expected, failure.rule),
+                failure.start,
+                failure.end,
+            ),
+            error => Diagnostic::error(codes::CONVERSION_FAILURE, format!("Could not build the syntax tree: {}", error), 0, 0),
+        };
+        diagnostic.with_note("The grammar accepts this code, this is a bug of the parser")
+    }
+}
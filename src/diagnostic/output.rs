@@ -0,0 +1,263 @@
+use std::fmt::Write;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::line_index::LineIndex;
+
+/// Version of the JSON schema, only increased when a field changes meaning or disappears
+pub const JSON_VERSION: u32 = 1;
+
+const TOOL_NAME: &str = "leekwars_parser";
+
+/// The diagnostics found in a source, with what the output formats need to locate them
+#[derive(Debug, Clone, Copy)]
+pub struct SourceDiagnostics<'a> {
+    /// Written as is, with `/` as separator in SARIF
+    pub path: &'a str,
+    pub source: &'a str,
+    pub diagnostics: &'a [Diagnostic],
+}
+
+/// A line and a column, both one-based
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+/// Serialises the diagnostics as JSON.
+///
+/// ```text
+/// { "version": 1, "files": [{ "path": "main.leek", "diagnostics": [{
+///     "severity": "error" | "warning" | "note", "code": "E0001", "message": "...",
+///     "span": { "start": 0, "end": 3 }, "range": { "start": { "line": 1, "column": 1 }, "end": ... },
+///     "label": "..." | null,
+///     "secondary_labels": [{ "span": ..., "range": ..., "message": "..." }],
+///     "notes": ["..."],
+///     "fixes": [{ "message": "...", "span": ..., "range": ..., "replacement": "..." }]
+/// }] }] }
+/// ```
+///
+/// Spans are byte offsets, lines and columns are one-based and columns count characters.
+pub fn to_json(files: &[SourceDiagnostics]) -> String {
+    let mut output = format!("{{\"version\":{},\"files\":[", JSON_VERSION);
+    for (file_index, file) in files.iter().enumerate() {
+        let index = LineIndex::new(file.source);
+        let range = |start, end| {
+            let (start, end) = (char_position(&index, file.source, start), char_position(&index, file.source, end));
+            format!(
+                "\"span\":{{\"start\":{},\"end\":{}}},\"range\":{{\"start\":{},\"end\":{}}}",
+                start.0, end.0, json_position(start.1), json_position(end.1),
+            )
+        };
+        separate(&mut output, file_index);
+        let _ = write!(output, "{{\"path\":{},\"diagnostics\":[", json_string(file.path));
+        for (diagnostic_index, diagnostic) in file.diagnostics.iter().enumerate() {
+            separate(&mut output, diagnostic_index);
+            let _ = write!(
+                output,
+                "{{\"severity\":{},\"code\":{},\"message\":{},{},\"label\":{},",
+                json_string(&diagnostic.severity.to_string()),
+                json_string(diagnostic.code),
+                json_string(&diagnostic.message),
+                range(diagnostic.start, diagnostic.end),
+                diagnostic.label.as_deref().map_or(String::from("null"), json_string),
+            );
+            let labels = diagnostic.secondary_labels.iter()
+                .map(|label| format!("{{{},\"message\":{}}}", range(label.start, label.end), json_string(&label.message)));
+            let notes = diagnostic.notes.iter().map(|note| json_string(note));
+            let fixes = diagnostic.fixes.iter().map(|fix| format!(
+                "{{\"message\":{},{},\"replacement\":{}}}",
+                json_string(&fix.message),
+                range(fix.start, fix.end),
+                json_string(&fix.replacement),
+            ));
+            let _ = write!(
+                output,
+                "\"secondary_labels\":[{}],\"notes\":[{}],\"fixes\":[{}]}}",
+                labels.collect::<Vec<_>>().join(","),
+                notes.collect::<Vec<_>>().join(","),
+                fixes.collect::<Vec<_>>().join(","),
+            );
+        }
+        output.push_str("]}");
+    }
+    output.push_str("]}");
+    output
+}
+
+/// Serialises the diagnostics as a SARIF 2.1.0 log with a single run.
+///
+/// Columns are counted in UTF-16 code units, the SARIF default. Notes are appended to the
+/// message of their result and fixes become SARIF fixes.
+pub fn to_sarif(files: &[SourceDiagnostics]) -> String {
+    let mut codes: Vec<&str> = files.iter()
+        .flat_map(|file| file.diagnostics.iter().map(|diagnostic| diagnostic.code))
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+    let rules = codes.iter().map(|code| format!("{{\"id\":{}}}", json_string(code))).collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for file in files {
+        let index = LineIndex::new(file.source);
+        let uri = json_string(&uri(file.path));
+        let region = |start: usize, end: usize| {
+            let (start_position, end_position) = (utf16_position(&index, start), utf16_position(&index, end));
+            format!(
+                "{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{},\"byteOffset\":{},\"byteLength\":{}}}",
+                start_position.line, start_position.column, end_position.line, end_position.column, start, end.saturating_sub(start),
+            )
+        };
+        let location = |start, end| format!(
+            "\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\"region\":{}}}",
+            uri, region(start, end),
+        );
+        for diagnostic in file.diagnostics {
+            let mut message = diagnostic.message.clone();
+            for note in &diagnostic.notes {
+                let _ = write!(message, "\nnote: {}", note);
+            }
+            let mut result = format!(
+                "{{\"ruleId\":{},\"ruleIndex\":{},\"level\":{},\"message\":{{\"text\":{}}},\"locations\":[{{{}}}]",
+                json_string(diagnostic.code),
+                codes.binary_search(&diagnostic.code).unwrap_or_default(),
+                json_string(match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Note => "note",
+                }),
+                json_string(&message),
+                location(diagnostic.start, diagnostic.end),
+            );
+            if !diagnostic.secondary_labels.is_empty() {
+                let locations = diagnostic.secondary_labels.iter().enumerate().map(|(id, label)| format!(
+                    "{{\"id\":{},{},\"message\":{{\"text\":{}}}}}",
+                    id, location(label.start, label.end), json_string(&label.message),
+                ));
+                let _ = write!(result, ",\"relatedLocations\":[{}]", locations.collect::<Vec<_>>().join(","));
+            }
+            if !diagnostic.fixes.is_empty() {
+                let fixes = diagnostic.fixes.iter().map(|fix| format!(
+                    "{{\"description\":{{\"text\":{}}},\"artifactChanges\":[{{\"artifactLocation\":{{\"uri\":{}}},\"replacements\":[{{\"deletedRegion\":{},\"insertedContent\":{{\"text\":{}}}}}]}}]}}",
+                    json_string(&fix.message), uri, region(fix.start, fix.end), json_string(&fix.replacement),
+                ));
+                let _ = write!(result, ",\"fixes\":[{}]", fixes.collect::<Vec<_>>().join(","));
+            }
+            result.push('}');
+            results.push(result);
+        }
+    }
+
+    format!(
+        "{{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"version\":\"2.1.0\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":{},\"version\":{},\"rules\":[{}]}}}},\"columnKind\":\"utf16CodeUnits\",\"results\":[{}]}}]}}",
+        json_string(TOOL_NAME),
+        json_string(env!("CARGO_PKG_VERSION")),
+        rules.join(","),
+        results.join(","),
+    )
+}
+
+/// Serialises the diagnostics as checkstyle XML, one `<error>` per diagnostic.
+///
+/// The code goes in the `source` attribute, as `leekwars_parser.E0001`. Columns count characters.
+pub fn to_checkstyle(files: &[SourceDiagnostics]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    for file in files {
+        let index = LineIndex::new(file.source);
+        let _ = writeln!(output, "  <file name=\"{}\">", xml_escape(file.path));
+        for diagnostic in file.diagnostics {
+            let (_, position) = char_position(&index, file.source, diagnostic.start);
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "info",
+            };
+            let _ = writeln!(
+                output,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}.{}\"/>",
+                position.line, position.column, severity, xml_escape(&diagnostic.message), TOOL_NAME, diagnostic.code,
+            );
+        }
+        output.push_str("  </file>\n");
+    }
+    output.push_str("</checkstyle>\n");
+    output
+}
+
+/// The offset, clamped to the source and its character boundaries, and its position
+fn char_position(index: &LineIndex, source: &str, offset: usize) -> (usize, Position) {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let position = index.line_column(offset);
+    let column = source[offset - position.column..offset].chars().count();
+    (offset, Position { line: position.line + 1, column: column + 1 })
+}
+
+fn utf16_position(index: &LineIndex, offset: usize) -> Position {
+    let position = index.line_column_utf16(offset);
+    Position { line: position.line + 1, column: position.column + 1 }
+}
+
+fn json_position(position: Position) -> String {
+    format!("{{\"line\":{},\"column\":{}}}", position.line, position.column)
+}
+
+fn separate(output: &mut String, index: usize) {
+    if index > 0 {
+        output.push(',');
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + 2);
+    output.push('"');
+    for char in text.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            char if char < ' ' => {
+                let _ = write!(output, "\\u{:04x}", char as u32);
+            }
+            char => output.push(char),
+        }
+    }
+    output.push('"');
+    output
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            '\n' => output.push_str("&#10;"),
+            '\r' => output.push_str("&#13;"),
+            '\t' => output.push_str("&#9;"),
+            // Not allowed in XML 1.0
+            char if char < ' ' => output.push('\u{fffd}'),
+            char => output.push(char),
+        }
+    }
+    output
+}
+
+/// A relative URI reference for a path, percent-encoding what URIs don't allow
+fn uri(path: &str) -> String {
+    let mut output = String::with_capacity(path.len());
+    for byte in path.replace('\\', "/").bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/!$&'()*+,;=:@".contains(&byte) {
+            output.push(byte as char);
+        } else {
+            let _ = write!(output, "%{:02X}", byte);
+        }
+    }
+    output
+}
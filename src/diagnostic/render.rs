@@ -0,0 +1,128 @@
+use std::fmt::Write;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::line_index::LineIndex;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const GREEN: &str = "\x1b[1;32m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// A label once placed on its line
+struct Mark<'a> {
+    line: usize,
+    /// Byte range in the line
+    start: usize,
+    end: usize,
+    primary: bool,
+    message: Option<&'a str>,
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic for a terminal, with the lines of `source` it points at underlined.
+    /// `path` names the source in the location line, `colored` adds ANSI colours.
+    pub fn render(&self, path: &str, source: &str, colored: bool) -> String {
+        let index = LineIndex::new(source);
+        let paint = |style: &str, text: &str| if colored {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        };
+        let severity_style = match self.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Note => GREEN,
+        };
+
+        let primary = mark(&index, source, self.start, self.end, true, self.label.as_deref());
+        let location = format!(
+            "{}:{}:{}",
+            path,
+            primary.line + 1,
+            line_text(&index, source, primary.line)[..primary.start].chars().count() + 1,
+        );
+        let mut marks = vec![primary];
+        for label in &self.secondary_labels {
+            marks.push(mark(&index, source, label.start, label.end, false, Some(&label.message)));
+        }
+        marks.sort_by_key(|mark| (mark.line, !mark.primary, mark.start));
+        let last_line = marks.iter().map(|mark| mark.line).max().unwrap_or(0);
+        let gutter = " ".repeat((last_line + 1).to_string().len());
+
+        let mut output = String::new();
+        let _ = writeln!(output, "{}{}", paint(severity_style, &format!("{}[{}]", self.severity, self.code)), paint(BOLD, &format!(": {}", self.message)));
+        let _ = writeln!(output, "{}{} {}", gutter, paint(BLUE, "-->"), location);
+        let _ = writeln!(output, "{} {}", gutter, paint(BLUE, "|"));
+
+        let mut previous_line = None;
+        for mark in &marks {
+            let line = line_text(&index, source, mark.line);
+            if previous_line != Some(mark.line) {
+                if previous_line.is_some_and(|previous| previous + 1 < mark.line) {
+                    let _ = writeln!(output, "{}", paint(BLUE, "..."));
+                }
+                let number = format!("{:>width$} |", mark.line + 1, width = gutter.len());
+                let _ = writeln!(output, "{} {}", paint(BLUE, &number), line);
+                previous_line = Some(mark.line);
+            }
+            let underline = if mark.primary { "^" } else { "-" }.repeat(line[mark.start..mark.end].chars().count().max(1));
+            let underline = match mark.message {
+                Some(message) => format!("{} {}", underline, message),
+                None => underline,
+            };
+            let style = if mark.primary { severity_style } else { BLUE };
+            let _ = writeln!(output, "{} {} {}{}", gutter, paint(BLUE, "|"), indentation(&line[..mark.start]), paint(style, &underline));
+        }
+        if !self.notes.is_empty() || !self.fixes.is_empty() {
+            let _ = writeln!(output, "{} {}", gutter, paint(BLUE, "|"));
+        }
+
+        for note in &self.notes {
+            let _ = writeln!(output, "{} {} {}", gutter, paint(BLUE, "="), format_args!("{}: {}", paint(BOLD, "note"), note));
+        }
+        for fix in &self.fixes {
+            let _ = writeln!(output, "{} {} {}: {}", gutter, paint(BLUE, "="), paint(BOLD, "help"), fix.message);
+            // Only the fixes that stay in a line are shown applied
+            let line = index.line_column(fix.start).line;
+            let Some(range) = index.line_range(line) else {
+                continue;
+            };
+            if let (Some(before), Some(after)) = (source.get(range.start..fix.start), source.get(fix.end..range.end)) {
+                let number = format!("{:>width$} |", line + 1, width = gutter.len());
+                let _ = writeln!(output, "{} {}{}{}", paint(BLUE, &number), before, fix.replacement, after);
+            }
+        }
+        output
+    }
+}
+
+fn mark<'a>(index: &LineIndex, source: &str, start: usize, end: usize, primary: bool, message: Option<&'a str>) -> Mark<'a> {
+    let line = index.line_column(start).line;
+    let text = line_text(index, source, line);
+    let line_start = index.line_range(line).map_or(0, |range| range.start);
+    // A range over several lines is underlined up to the end of its first line
+    let start = floor_char_boundary(text, start.saturating_sub(line_start));
+    let end = floor_char_boundary(text, end.saturating_sub(line_start)).max(start);
+    Mark {
+        line,
+        start,
+        end,
+        primary,
+        message,
+    }
+}
+
+fn line_text<'a>(index: &LineIndex, source: &'a str, line: usize) -> &'a str {
+    index.line_range(line).map_or("", |range| &source[range])
+}
+
+/// The last character boundary of `text` at or before `offset`, clamped to its length
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    (0..=offset.min(text.len())).rev().find(|offset| text.is_char_boundary(*offset)).unwrap_or(0)
+}
+
+/// Blanks that line up with `text`, keeping its tabs
+fn indentation(text: &str) -> String {
+    text.chars().map(|char| if char == '\t' { '\t' } else { ' ' }).collect()
+}
@@ -0,0 +1,120 @@
+use std::any::Any;
+use from_pest::FromPest;
+use pest::Parser;
+use crate::ast::binary_expression::PowerExpression;
+use crate::ast::block_statement::BlockStatement;
+use crate::ast::expression::Expression;
+use crate::ast::lsv4root::Lsv4Root;
+use crate::ast::statement::Statement;
+use crate::ast::{AstNodeMeta, Span, VisitNodes};
+use crate::lsv4::{Lsv4Parser, Rule};
+use crate::{check_depth, parse, validation, ParseErrors, ParseOptions};
+
+/// The replacement of a byte range of a source
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// The source with the edit made, `None` if the range isn't in it
+    pub fn apply(&self, source: &str) -> Option<String> {
+        let (before, after) = (source.get(..self.start)?, source.get(self.end..)?);
+        Some([before, &self.replacement, after].concat())
+    }
+}
+
+/// Parses `source` again after `edit`, starting from the tree [`parse`] built before the edit.
+///
+/// `source` is the edited source. Only the innermost block whose braces contain the edit is
+/// parsed again, the other nodes are kept and the spans after the edit are moved. When the edit
+/// isn't inside a block, or the block doesn't end at its previous `}` anymore, or anything is
+/// wrong with it, the whole source is parsed again. The result is always the one of [`parse`].
+pub fn reparse(previous: Lsv4Root, edit: &TextEdit, source: &str, options: ParseOptions) -> Result<Lsv4Root, ParseErrors> {
+    match reparse_block(previous, edit, source, options) {
+        Some(root) => Ok(root),
+        None => parse(source, options),
+    }
+}
+
+/// The tree after the edit, `None` when the whole source has to be parsed again
+pub(crate) fn reparse_block(mut root: Lsv4Root, edit: &TextEdit, source: &str, options: ParseOptions) -> Option<Lsv4Root> {
+    // The end of input is at the end of the source the tree was built from
+    let previous_len = root.eoi.span().end;
+    let inserted = edit.replacement.len();
+    if edit.start > edit.end || edit.end > previous_len
+        || source.len() + (edit.end - edit.start) != previous_len + inserted
+        || source.get(edit.start..edit.start + inserted) != Some(edit.replacement.as_str())
+    {
+        return None;
+    }
+    let moved = |offset: usize| offset - edit.end + edit.start + inserted;
+
+    // The innermost block around the edit, and the classes around it
+    let mut block = None;
+    let mut classes = Vec::new();
+    // The levels of the nodes around the edit, which are the ones the block is nested in, as
+    // `check_depth` counts them
+    let (mut depth, mut in_power) = (0, false);
+    root.visit_nodes(&mut |meta: &mut AstNodeMeta, data: &mut dyn Any| {
+        let span = meta.span;
+        if span.start > edit.start || edit.end > span.end {
+            return false;
+        }
+        let is_power = data.is::<PowerExpression>();
+        if data.is::<Statement>() || data.is::<Expression>() || is_power && in_power {
+            depth += 1;
+        }
+        in_power = is_power;
+        if data.is::<BlockStatement>() && span.start < edit.start && edit.end < span.end {
+            block = Some((span, depth));
+        }
+        if let Some(Statement::ClassDeclaration(_)) = data.downcast_ref::<Statement>() {
+            classes.push(span);
+        }
+        true
+    });
+    let (block, depth) = block?;
+    let in_class = classes.iter().any(|class| class.start < block.start && block.end <= class.end);
+
+    let new_block = Span { start: block.start, end: moved(block.end) };
+    let text = source.get(block.start..)?;
+    let pairs = Lsv4Parser::parse(Rule::BlockStatement, text).ok()?;
+    let pair = pairs.peek()?;
+    if pair.as_span().end() != new_block.len() || check_depth(&pairs, depth).is_some()
+        || !validation::validate_nested(pairs.clone(), options.version, in_class).is_empty() {
+        return None;
+    }
+    let mut data = BlockStatement::from_pest(&mut pairs.clone()).ok()?;
+    // Its spans are relative to the block
+    data.visit_nodes(&mut |meta: &mut AstNodeMeta, _: &mut dyn Any| {
+        meta.span = Span { start: meta.span.start + block.start, end: meta.span.end + block.start };
+        true
+    });
+
+    let mut data = Some(data);
+    root.visit_nodes(&mut |meta: &mut AstNodeMeta, node: &mut dyn Any| {
+        let span = meta.span;
+        if span == block {
+            if let Some(node) = node.downcast_mut::<BlockStatement>() {
+                if let Some(data) = data.take() {
+                    *node = data;
+                }
+                meta.span = new_block;
+                return false;
+            }
+        }
+        if span.start >= block.end {
+            meta.span = Span { start: moved(span.start), end: moved(span.end) };
+        } else if span.end >= block.end {
+            meta.span.end = moved(span.end);
+        } else {
+            // Before the block
+            return false;
+        }
+        true
+    });
+    data.is_none().then_some(root)
+}
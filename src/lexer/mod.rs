@@ -0,0 +1,169 @@
+/// What a token is, the lexer doesn't tell keywords from identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TokenKind {
+    /// A run of spaces, tabs and line terminators
+    Whitespace,
+    LineComment,
+    BlockComment,
+    /// `_`, letters and digits, not starting with a digit
+    Word,
+    Number(NumberKind),
+    String,
+    /// A single ASCII punctuation character, operators are made of several of them
+    Punct,
+    /// Any other character, as `∞` or `π`
+    Other,
+    /// A string or a block comment missing its end, up to the end of the source
+    Unterminated,
+}
+
+/// The rule of the grammar a number literal matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum NumberKind {
+    Hex,
+    Bin,
+    Real,
+    Integer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl TokenKind {
+    /// Whitespace and comments, which the grammar skips between tokens
+    pub fn is_trivia(self) -> bool {
+        matches!(self, TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment)
+    }
+}
+
+/// The words `Identifier` doesn't accept, the `Reserved` rule of the grammar
+pub(crate) const RESERVED: [&str; 28] = [
+    "true", "false", "null", "if", "else", "var", "global", "in", "as", "for", "while", "do", "and",
+    "or", "xor", "not", "typeof", "instanceof", "function", "return", "break", "continue", "class",
+    "extends", "private", "public", "protected", "static",
+];
+
+/// The keywords `Identifier` also accepts, the `VersionedKeyword` rule of the grammar
+pub(crate) const VERSIONED_KEYWORDS: [&str; 9] = [
+    "this", "super", "new", "enum", "switch", "case", "default", "interface", "implements",
+];
+
+/// Splits a source into tokens, covering all of it.
+///
+/// The tokens end where the rules of the grammar end: a number swallows the letters that follow
+/// it and `1..2` is `1` followed by `..`. A number never starts with `.`, `.5` is `.` and `5`.
+pub(crate) fn lex(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::with_capacity(source.len() / 3);
+    let mut start = 0;
+    while start < source.len() {
+        let token = token_at(source, start);
+        tokens.push(token);
+        start = token.end;
+    }
+    tokens
+}
+
+/// The token starting at `start`, which is before the end of the source
+pub(crate) fn token_at(source: &str, start: usize) -> Token {
+    let bytes = source.as_bytes();
+    let (kind, end) = match bytes[start] {
+        b' ' | b'\t' | b'\n' | b'\r' => {
+            (TokenKind::Whitespace, scan(bytes, start, |byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r')))
+        }
+        b'/' if bytes.get(start + 1) == Some(&b'/') => {
+            (TokenKind::LineComment, scan(bytes, start, |byte| !matches!(byte, b'\n' | b'\r')))
+        }
+        b'/' if bytes.get(start + 1) == Some(&b'*') => match source[start + 2..].find("*/") {
+            Some(offset) => (TokenKind::BlockComment, start + 2 + offset + 2),
+            None => (TokenKind::Unterminated, bytes.len()),
+        },
+        b'_' | b'a'..=b'z' | b'A'..=b'Z' => (TokenKind::Word, identifier_tail(bytes, start)),
+        b'0'..=b'9' => {
+            let (kind, end) = number(bytes, start);
+            (TokenKind::Number(kind), end)
+        }
+        quote @ (b'\'' | b'"') => string(bytes, start, quote),
+        byte if byte.is_ascii_punctuation() => (TokenKind::Punct, start + 1),
+        _ => {
+            let char = source[start..].chars().next().map_or(1, char::len_utf8);
+            (TokenKind::Other, start + char)
+        }
+    };
+    Token { kind, start, end }
+}
+
+fn scan(bytes: &[u8], start: usize, accept: impl Fn(u8) -> bool) -> usize {
+    bytes[start..].iter().position(|byte| !accept(*byte)).map_or(bytes.len(), |length| start + length)
+}
+
+/// The end of the `IdentifierTail*` starting at `start`
+fn identifier_tail(bytes: &[u8], start: usize) -> usize {
+    scan(bytes, start, |byte| byte == b'_' || byte.is_ascii_alphanumeric())
+}
+
+/// `Digits`, `None` if there is no digit at `start`
+fn digits(bytes: &[u8], start: usize) -> Option<usize> {
+    bytes.get(start).filter(|byte| byte.is_ascii_digit())?;
+    Some(scan(bytes, start, |byte| byte == b'_' || byte.is_ascii_digit()))
+}
+
+fn exponent(bytes: &[u8], start: usize) -> Option<usize> {
+    if !matches!(bytes.get(start), Some(b'e' | b'E')) {
+        return None;
+    }
+    let sign = matches!(bytes.get(start + 1), Some(b'+' | b'-')) as usize;
+    digits(bytes, start + 1 + sign)
+}
+
+/// The number starting with the digit at `start`, tried in the order of the `Number` rule
+fn number(bytes: &[u8], start: usize) -> (NumberKind, usize) {
+    if bytes[start] == b'0' {
+        match bytes.get(start + 1) {
+            Some(b'x' | b'X') => return (NumberKind::Hex, identifier_tail(bytes, start + 2)),
+            Some(b'b' | b'B') => return (NumberKind::Bin, identifier_tail(bytes, start + 2)),
+            _ => {}
+        }
+    }
+    let end = digits(bytes, start).unwrap_or(start);
+    if bytes.get(end) == Some(&b'.') && bytes.get(end + 1) != Some(&b'.') {
+        let fraction = digits(bytes, end + 1).unwrap_or(end + 1);
+        let exponent = exponent(bytes, fraction).unwrap_or(fraction);
+        return (NumberKind::Real, identifier_tail(bytes, exponent));
+    }
+    match exponent(bytes, end) {
+        Some(exponent) => (NumberKind::Real, identifier_tail(bytes, exponent)),
+        None => (NumberKind::Integer, identifier_tail(bytes, end)),
+    }
+}
+
+/// The end of the real starting with the `.` at `start`, which the lexer splits before the digits
+pub(crate) fn leading_dot_real(source: &str, start: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let fraction = digits(bytes, start + 1)?;
+    let exponent = exponent(bytes, fraction).unwrap_or(fraction);
+    Some(identifier_tail(bytes, exponent))
+}
+
+/// A string, its escape sequences taking the character after the backslash whatever it is
+fn string(bytes: &[u8], start: usize, quote: u8) -> (TokenKind, usize) {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => {
+                index += 1;
+                // Up to the end of the escaped character
+                index += 1;
+                while index < bytes.len() && (bytes[index] & 0xC0) == 0x80 {
+                    index += 1;
+                }
+            }
+            byte if byte == quote => return (TokenKind::String, index + 1),
+            _ => index += 1,
+        }
+    }
+    (TokenKind::Unterminated, bytes.len())
+}
@@ -0,0 +1,238 @@
+pub mod ast;
+pub mod utils;
+pub mod validation;
+pub mod project;
+pub mod version;
+pub mod line_index;
+pub mod diagnostic;
+pub mod incremental;
+pub mod token;
+pub mod arena;
+mod lexer;
+mod parser;
+#[cfg(test)]
+mod test;
+
+use std::fmt;
+use from_pest::FromPest;
+use pest::iterators::Pairs;
+use pest::{Parser, Token};
+use crate::ast::lsv4root::Lsv4Root;
+use crate::lsv4::Rule;
+use crate::diagnostic::{codes, Diagnostic};
+use crate::version::LanguageVersion;
+
+pub mod lsv4 {
+    use pest_derive::Parser;
+
+    #[derive(Parser)]
+    #[grammar = "grammar/lsv4.pest"]
+    pub struct Lsv4Parser; // Parser for Leekscript V4
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions {
+    pub version: LanguageVersion,
+    pub backend: Backend,
+}
+
+/// How the source is turned into pairs, both give the same trees
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backend {
+    /// The parser pest generates from the grammar. It backtracks on nested parentheses, brackets
+    /// and calls, each level making the parse a few times longer.
+    #[default]
+    Pest,
+    /// A hand-written lexer and parser, several times faster. The sources it doesn't parse go
+    /// through pest, which reports the syntax errors.
+    HandWritten,
+}
+
+/// The deepest a syntax tree is built for, in levels of statements, expressions, types and
+/// operands of `**`, which is how the trees recurse. `f(f(1))` is 3 levels deep, `{ a; }` 2.
+/// Building, validating, printing and visiting a tree grow the stack as they go deeper, cloning,
+/// comparing and dropping it don't: this keeps them within the 2 MiB stack of a spawned thread,
+/// a level of expression taking up to 25 pairs.
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// Whether a pair of `rule` in one of `parent` is a level deeper than its parent
+pub(crate) fn is_level(rule: Rule, parent: Option<Rule>) -> bool {
+    match rule {
+        Rule::Statement | Rule::Expression | Rule::Type => true,
+        // `a ** b ** c` nests the operands of `**` without an expression in between
+        Rule::PowerExpression => parent == Some(Rule::PowerExpression),
+        _ => false,
+    }
+}
+
+/// Reports the first level nested deeper than [`MAX_DEPTH`], `depth` being the level `pairs`
+/// are at
+pub(crate) fn check_depth(pairs: &Pairs<Rule>, mut depth: usize) -> Option<Diagnostic> {
+    // The rules of the pairs around the token, and whether each is a level
+    let mut open: Vec<(Rule, bool)> = Vec::new();
+    let mut tokens = pairs.clone().tokens();
+    while let Some(token) = tokens.next() {
+        let Token::Start { rule, pos } = token else {
+            if open.pop().is_some_and(|(_, level)| level) {
+                depth -= 1;
+            }
+            continue;
+        };
+        let level = is_level(rule, open.last().map(|&(parent, _)| parent));
+        open.push((rule, level));
+        if !level {
+            continue;
+        }
+        depth += 1;
+        if depth > MAX_DEPTH {
+            let start = pos.pos();
+            let mut nesting = 0;
+            let end = tokens.find_map(|token| match token {
+                Token::Start { .. } => {
+                    nesting += 1;
+                    None
+                }
+                Token::End { pos, .. } if nesting == 0 => Some(pos.pos()),
+                Token::End { .. } => {
+                    nesting -= 1;
+                    None
+                }
+            });
+            return Some(Diagnostic::error(codes::TOO_DEEP, "The code is nested too deeply to be parsed", start, end.unwrap_or(start))
+                .with_note(format!("Statements and expressions can be nested at most {} levels deep", MAX_DEPTH)));
+        }
+    }
+    None
+}
+
+/// Runs `f`, on a new part of the stack if the current one is close to its end. The functions
+/// recursing into a tree go through it at each level. pest fails the rules it enters with less
+/// than 64 KiB left, the pairs of the hand-written parser are made with its rules.
+pub(crate) fn grow_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(128 * 1024, 1024 * 1024, f)
+}
+
+/// Parses a source with `lsv4_root`
+fn parse_pairs(source: &str, backend: Backend) -> Result<Pairs<'_, Rule>, pest::error::Error<Rule>> {
+    match backend {
+        Backend::HandWritten => parser::parse(source).map_or_else(|| lsv4::Lsv4Parser::parse(Rule::lsv4_root, source), Ok),
+        Backend::Pest => lsv4::Lsv4Parser::parse(Rule::lsv4_root, source),
+    }
+}
+
+/// The problems that prevented a source from being parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseErrors {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, diagnostic) in self.diagnostics.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}..{}: {}", diagnostic.start, diagnostic.end, diagnostic.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
+/// Parses a LeekScript source into its syntax tree.
+///
+/// The source is rejected if it doesn't match the grammar or if the validation finds a problem
+/// in it, such as an invalid escape sequence or a construct `options.version` doesn't have.
+pub fn parse(source: &str, options: ParseOptions) -> Result<Lsv4Root, ParseErrors> {
+    let pairs = validated_pairs(source, options)?;
+    Lsv4Root::from_pest(&mut pairs.clone()).map_err(|error| ParseErrors {
+        diagnostics: vec![Diagnostic::from(error)],
+    })
+}
+
+/// The pairs of a source that matches the grammar and passes the validation
+fn validated_pairs(source: &str, options: ParseOptions) -> Result<Pairs<'_, Rule>, ParseErrors> {
+    let pairs = parse_pairs(source, options.backend).map_err(|error| ParseErrors {
+        diagnostics: vec![Diagnostic::from(error)],
+    })?;
+    if let Some(diagnostic) = check_depth(&pairs, 0) {
+        return Err(ParseErrors { diagnostics: vec![diagnostic] });
+    }
+
+    let diagnostics = validation::validate(pairs.clone(), options.version);
+    if !diagnostics.is_empty() {
+        return Err(ParseErrors { diagnostics });
+    }
+    Ok(pairs)
+}
+
+/// A syntax tree built in spite of the syntax errors, with everything that was found wrong
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecoveredParse {
+    /// Only missing when the tree couldn't be built at all
+    pub root: Option<Lsv4Root>,
+    /// Sorted by position
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses a LeekScript source, recovering from the syntax errors.
+///
+/// What can't be parsed is skipped up to the next `;`, `}` or statement keyword and kept in the
+/// tree as `Statement::Error` or `Expression::Error`, a missing expression or statement body as
+/// `Expression::Missing` or `Statement::Missing`. A source without syntax errors gives the same
+/// tree as [`parse`].
+pub fn parse_recovering(source: &str, options: ParseOptions) -> RecoveredParse {
+    let mut diagnostics = Vec::new();
+    let pairs = match parse_pairs(source, options.backend) {
+        Ok(pairs) => Ok(pairs),
+        Err(error) => {
+            // The first error is reported with what the grammar expected there, the recovery
+            // nodes only describe the others
+            let error = Diagnostic::from(error);
+            let recovered = lsv4::Lsv4Parser::parse(Rule::recovering_root, source);
+            // A tree too deep to walk is reported below
+            if let Some(pairs) = recovered.as_ref().ok().filter(|pairs| check_depth(pairs, 0).is_none()) {
+                recovery_diagnostics(pairs.clone(), &error, &mut diagnostics);
+            }
+            diagnostics.push(error);
+            recovered
+        }
+    };
+
+    let root = pairs.ok().and_then(|pairs| {
+        if let Some(diagnostic) = check_depth(&pairs, 0) {
+            diagnostics.push(diagnostic);
+            return None;
+        }
+        diagnostics.extend(validation::validate(pairs.clone(), options.version));
+        Lsv4Root::from_pest(&mut pairs.clone())
+            .map_err(|error| diagnostics.push(Diagnostic::from(error)))
+            .ok()
+    });
+
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.start, diagnostic.end));
+    RecoveredParse { root, diagnostics }
+}
+
+/// Reports the error and missing nodes left by the recovering parser, except the one `error` is in
+fn recovery_diagnostics(pairs: Pairs<Rule>, error: &Diagnostic, diagnostics: &mut Vec<Diagnostic>) {
+    for pair in pairs {
+        let (code, message) = match pair.as_rule() {
+            Rule::ErrorStatement => (codes::INVALID_STATEMENT, "Invalid statement"),
+            Rule::ErrorExpression => (codes::INVALID_EXPRESSION, "Invalid expression"),
+            Rule::MissingStatement => (codes::MISSING_STATEMENT, "Missing statement"),
+            Rule::MissingExpression => (codes::MISSING_EXPRESSION, "Missing expression"),
+            _ => {
+                grow_stack(|| recovery_diagnostics(pair.into_inner(), error, diagnostics));
+                continue;
+            }
+        };
+        // The span of a pair also covers the whitespace that follows it
+        let start = pair.as_span().start();
+        let end = start + pair.as_str().trim_end().len();
+        if !(start..=end).contains(&error.start) {
+            diagnostics.push(Diagnostic::error(code, message, start, end));
+        }
+    }
+}
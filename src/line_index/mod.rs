@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+/// A position in the source, both zero-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    /// In bytes or in UTF-16 code units, depending on the conversion
+    pub column: usize,
+}
+
+/// Converts the byte offsets of a source to lines and columns and back.
+///
+/// Lines end with `\n`, `\r\n` or `\r`, like in the grammar. The columns are counted in bytes
+/// (UTF-8) or in UTF-16 code units, which is what editors speaking LSP expect.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineIndex {
+    /// The offset each line starts at
+    line_starts: Vec<usize>,
+    /// The offset each line ends at, before its line terminator
+    line_ends: Vec<usize>,
+    /// The offset and UTF-8 length of the characters longer than a byte, in order
+    wide_chars: Vec<(usize, usize)>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut line_ends = Vec::new();
+        let mut wide_chars = Vec::new();
+        let mut chars = source.char_indices().peekable();
+        while let Some((offset, char)) = chars.next() {
+            match char {
+                '\r' if chars.peek().map(|(_, next)| *next) == Some('\n') => {
+                    chars.next();
+                    line_ends.push(offset);
+                    line_starts.push(offset + 2);
+                }
+                '\n' | '\r' => {
+                    line_ends.push(offset);
+                    line_starts.push(offset + 1);
+                }
+                _ if char.len_utf8() > 1 => wide_chars.push((offset, char.len_utf8())),
+                _ => {}
+            }
+        }
+        line_ends.push(source.len());
+        LineIndex {
+            line_starts,
+            line_ends,
+            wide_chars,
+            len: source.len(),
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The line and byte column of `offset`, which is clamped to the end of the source
+    pub fn line_column(&self, offset: usize) -> LineColumn {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        LineColumn {
+            line,
+            column: offset - self.line_starts[line],
+        }
+    }
+
+    /// The line and UTF-16 column of `offset`, which is clamped to the end of the source. An offset
+    /// in the middle of a character is that of the character.
+    pub fn line_column_utf16(&self, offset: usize) -> LineColumn {
+        let LineColumn { line, column } = self.line_column(offset);
+        let line_start = self.line_starts[line];
+        let offset = line_start + column;
+        let column = self.wide_chars_between(line_start, offset).fold(column, |column, &(char_offset, len)| {
+            if char_offset + len > offset {
+                column - (offset - char_offset)
+            } else {
+                column - len + utf16_len(len)
+            }
+        });
+        LineColumn { line, column }
+    }
+
+    /// The offset of a line and byte column, if the column is in the line and doesn't fall in the
+    /// middle of a character
+    pub fn offset(&self, position: LineColumn) -> Option<usize> {
+        let Range { start, end } = self.line_range(position.line)?;
+        let offset = start + position.column;
+        (offset <= end && self.is_char_boundary(offset)).then_some(offset)
+    }
+
+    /// The offset of a line and UTF-16 column, if the column is in the line and doesn't fall in
+    /// the middle of a character
+    pub fn offset_utf16(&self, position: LineColumn) -> Option<usize> {
+        let Range { start, end } = self.line_range(position.line)?;
+        let mut offset = start;
+        let mut column = 0;
+        for (char_offset, len) in self.wide_chars_between(start, end) {
+            if column + (char_offset - offset) >= position.column {
+                break;
+            }
+            column += char_offset - offset + utf16_len(*len);
+            offset = char_offset + len;
+        }
+        if column > position.column {
+            return None;
+        }
+        let offset = offset + position.column - column;
+        (offset <= end).then_some(offset)
+    }
+
+    /// The byte range of a line, without its line terminator
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        Some(*self.line_starts.get(line)?..*self.line_ends.get(line)?)
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        let next = self.wide_chars.partition_point(|(char_offset, _)| *char_offset < offset);
+        next == 0 || {
+            let (char_offset, len) = self.wide_chars[next - 1];
+            char_offset + len <= offset
+        }
+    }
+
+    fn wide_chars_between(&self, start: usize, end: usize) -> impl Iterator<Item = &(usize, usize)> {
+        let first = self.wide_chars.partition_point(|(offset, _)| *offset < start);
+        self.wide_chars[first..].iter().take_while(move |(offset, _)| *offset < end)
+    }
+}
+
+/// Only the characters outside of the basic plane take two UTF-16 code units, and four bytes
+fn utf16_len(utf8_len: usize) -> usize {
+    if utf8_len == 4 { 2 } else { 1 }
+}
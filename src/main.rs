@@ -1,21 +1,11 @@
-pub mod ast;
-pub mod utils;
-mod test;
-
+use std::io::IsTerminal;
 use from_pest::FromPest;
-use pest::iterators::Pairs;
+use pest::iterators::{Pair, Pairs};
 use pest::Parser;
-use crate::lsv4::Rule;
-use crate::utils::PrintAst;
-use pest::iterators::Pair;
-
-pub mod lsv4 {
-    use pest_derive::Parser;
-
-    #[derive(Parser)]
-    #[grammar = "grammar/lsv4.pest"]
-    pub struct Lsv4Parser; // Parser for Leekscript V4
-}
+use leekwars_parser::{ast, lsv4, utils, validation, version};
+use leekwars_parser::diagnostic::Diagnostic;
+use leekwars_parser::lsv4::Rule;
+use leekwars_parser::utils::PrintAst;
 
 fn display_pairs(pairs: Pairs<Rule>, level: usize, exclude_comments_and_whitespace: bool) -> String {
     let mut output = String::new();
@@ -24,7 +14,9 @@ fn display_pairs(pairs: Pairs<Rule>, level: usize, exclude_comments_and_whitespa
         let rule = pair.as_rule();
 
         fn concatenate_rules(pair: Pair<Rule>, output: &mut String, level: usize, exclude_comments_and_whitespace: bool) {
-            let inner_pairs: Vec<_> = pair.clone().into_inner().collect();
+            let inner_pairs: Vec<_> = pair.clone().into_inner()
+                .filter(|p| !exclude_comments_and_whitespace || (p.as_rule() != Rule::COMMENT && p.as_rule() != Rule::WHITESPACE))
+                .collect();
 
             if inner_pairs.len() == 1 {
                 let inner_pair = &inner_pairs[0];
@@ -56,12 +48,28 @@ fn display_pairs(pairs: Pairs<Rule>, level: usize, exclude_comments_and_whitespa
 fn main() {
     let input = r#"
     "#;
-    let mut pairs = lsv4::Lsv4Parser::parse(Rule::lsv4_root, input).unwrap_or_else(|e| panic!("{}", e));
+    let colored = std::io::stderr().is_terminal();
+    let mut pairs = match lsv4::Lsv4Parser::parse(Rule::lsv4_root, input) {
+        Ok(pairs) => pairs,
+        Err(error) => {
+            eprint!("{}", Diagnostic::from(error).render("input", input, colored));
+            std::process::exit(1);
+        }
+    };
 
     println!("{}", display_pairs(pairs.clone(), 0, true));
 
-    let root = ast::lsv4root::Lsv4Root::from_pest(&mut pairs)
-        .unwrap_or_else(|e| panic!("{}", e));
+    for diagnostic in validation::validate(pairs.clone(), version::LanguageVersion::default()) {
+        eprint!("{}", diagnostic.render("input", input, colored));
+    }
+
+    let root = match ast::lsv4root::Lsv4Root::from_pest(&mut pairs) {
+        Ok(root) => root,
+        Err(error) => {
+            eprint!("{}", Diagnostic::from(error).render("input", input, colored));
+            std::process::exit(1);
+        }
+    };
     println!("{:#?}", root);
 
     println!("Reconstructed file:");
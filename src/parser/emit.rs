@@ -0,0 +1,89 @@
+use pest::iterators::Pairs;
+use pest::{ParseResult, ParserState};
+use crate::lexer::{Token, TokenKind};
+use crate::grow_stack;
+use crate::lsv4::Rule;
+use super::{Element, Node};
+
+type State<'i> = Box<ParserState<'i, Rule>>;
+
+/// The pairs of a tree, made with the state pest's own rules go through.
+///
+/// `tokens` are all the tokens of the source, the whitespace and the comments become
+/// `WHITESPACE` and `COMMENT` pairs at the first skip they are found at, as pest does.
+pub(super) fn pairs<'i>(source: &'i str, tokens: &[Token], root: &Node) -> Option<Pairs<'i, Rule>> {
+    let mut emitter = Emitter { source, tokens, next: 0 };
+    pest::state(source, |state| emitter.node(state, root)).ok()
+}
+
+struct Emitter<'i, 't> {
+    source: &'i str,
+    tokens: &'t [Token],
+    /// The first token not emitted yet
+    next: usize,
+}
+
+impl<'i> Emitter<'i, '_> {
+    fn node(&mut self, state: State<'i>, node: &Node) -> ParseResult<State<'i>> {
+        if node.rule == Rule::EOI {
+            return state.rule(Rule::EOI, |state| state.end_of_input());
+        }
+        state.rule(node.rule, |mut state| {
+            for child in &node.children {
+                state = match *child {
+                    Element::Node(ref node) => grow_stack(|| self.node(state, node))?,
+                    Element::Text(start, end) => self.text(state, start, end)?,
+                    Element::Skip => self.skip(state)?,
+                };
+            }
+            Ok(state)
+        })
+    }
+
+    fn text(&mut self, state: State<'i>, start: usize, end: usize) -> ParseResult<State<'i>> {
+        // Whitespace or a comment the tree has no skip for
+        if state.position().pos() != start {
+            return Err(state);
+        }
+        while self.tokens.get(self.next).is_some_and(|token| token.end <= end) {
+            self.next += 1;
+        }
+        state.match_string(&self.source[start..end])
+    }
+
+    fn skip(&mut self, mut state: State<'i>) -> ParseResult<State<'i>> {
+        while let Some(&token) = self.tokens.get(self.next) {
+            if token.start != state.position().pos() || !token.kind.is_trivia() {
+                break;
+            }
+            self.next += 1;
+            let text = &self.source[token.start..token.end];
+            state = match token.kind {
+                TokenKind::LineComment => comment(state, Rule::SingleLineComment, text)?,
+                TokenKind::BlockComment => comment(state, Rule::MultiLineComment, text)?,
+                _ => whitespace(state, text)?,
+            };
+        }
+        Ok(state)
+    }
+}
+
+fn comment<'i>(state: State<'i>, rule: Rule, text: &str) -> ParseResult<State<'i>> {
+    state.rule(Rule::COMMENT, |state| state.rule(rule, |state| state.match_string(text)))
+}
+
+/// A `WHITESPACE` pair per space, tab or line terminator
+fn whitespace<'i>(mut state: State<'i>, mut text: &str) -> ParseResult<State<'i>> {
+    while !text.is_empty() {
+        let (rule, length) = match text.as_bytes() {
+            [b'\r', b'\n', ..] => (Rule::LineTerminator, 2),
+            [b'\r' | b'\n', ..] => (Rule::LineTerminator, 1),
+            [b'\t', ..] => (Rule::Tab, 1),
+            _ => (Rule::Space, 1),
+        };
+        let (unit, rest) = text.split_at(length);
+        state = state.rule(Rule::WHITESPACE, |state| state.rule(rule, |state| state.match_string(unit)))?;
+        text = rest;
+    }
+    Ok(state)
+}
@@ -0,0 +1,1320 @@
+mod emit;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use pest::iterators::Pairs;
+use crate::lexer::{leading_dot_real, lex, NumberKind, Token, TokenKind, RESERVED, VERSIONED_KEYWORDS};
+use crate::lsv4::Rule;
+use crate::{grow_stack, is_level, MAX_DEPTH};
+
+/// A node of the syntax tree, with the rule pest gives to its pair
+#[derive(Debug)]
+struct Node {
+    rule: Rule,
+    children: Vec<Element>,
+}
+
+type Tree = Rc<Node>;
+
+#[derive(Debug, Clone)]
+enum Element {
+    Node(Tree),
+    /// A byte range of the source, matched by the node
+    Text(usize, usize),
+    /// Where pest skips the whitespace and comments, between the elements of a sequence
+    Skip,
+}
+
+/// An alternative of an operator rule
+#[derive(Clone, Copy)]
+enum Operator {
+    Text(&'static str),
+    Symbol(Rule, &'static str),
+    Keyword(Rule, &'static str),
+}
+
+const ASSIGNMENT_OPERATOR: &[Operator] = &[
+    Operator::Symbol(Rule::Eq, "="), Operator::Text("+="), Operator::Text("-="), Operator::Text("**="),
+    Operator::Text("*="), Operator::Text("/="), Operator::Text("\\="), Operator::Text("%="), Operator::Text("&="),
+    Operator::Text("|="), Operator::Text("^="), Operator::Text("<<="), Operator::Text(">>>="), Operator::Text(">>="),
+];
+const LOGICAL_OR: &[Operator] = &[Operator::Text("||"), Operator::Keyword(Rule::Or, "or")];
+const LOGICAL_XOR: &[Operator] = &[Operator::Text("^^"), Operator::Keyword(Rule::Xor, "xor")];
+const LOGICAL_AND: &[Operator] = &[Operator::Text("&&"), Operator::Keyword(Rule::And, "and")];
+const EQUALITY_OPERATOR: &[Operator] = &[Operator::Text("==="), Operator::Text("!=="), Operator::Text("=="), Operator::Text("!=")];
+const RELATIONAL_OPERATOR: &[Operator] = &[
+    Operator::Text("<="), Operator::Text(">="), Operator::Text("<"), Operator::Text(">"),
+    Operator::Keyword(Rule::InstanceOf, "instanceof"), Operator::Keyword(Rule::In, "in"), Operator::Keyword(Rule::As, "as"),
+];
+const BOOLEAN_OR: &[Operator] = &[Operator::Text("|")];
+const BOOLEAN_XOR: &[Operator] = &[Operator::Text("^")];
+const BOOLEAN_AND: &[Operator] = &[Operator::Text("&")];
+const SHIFT_OPERATOR: &[Operator] = &[Operator::Text("<<"), Operator::Text(">>>"), Operator::Text(">>")];
+const ADDITIVE_OPERATOR: &[Operator] = &[Operator::Text("+"), Operator::Text("-")];
+const MULTIPLICATIVE_OPERATOR: &[Operator] = &[Operator::Text("*"), Operator::Text("/"), Operator::Text("\\"), Operator::Text("%")];
+const POWER_OPERATOR: &[Operator] = &[Operator::Text("**")];
+const UNARY_OPERATOR: &[Operator] = &[
+    Operator::Text("-"), Operator::Text("!"), Operator::Text("~"), Operator::Symbol(Rule::Reference, "@"),
+    Operator::Keyword(Rule::TypeOf, "typeof"), Operator::Keyword(Rule::Not, "not"),
+];
+const UPDATE_OPERATOR: &[Operator] = &[Operator::Text("++"), Operator::Text("--")];
+const ARROW: &[Operator] = &[Operator::Text("=>"), Operator::Text("->")];
+
+/// The binary expressions from the loosest to the tightest, with their operator
+const BINARY_LEVELS: [(Rule, Rule, &[Operator]); 11] = [
+    (Rule::LogicalORExpression, Rule::LogicalOR, LOGICAL_OR),
+    (Rule::LogicalXORExpression, Rule::LogicalXOR, LOGICAL_XOR),
+    (Rule::LogicalANDExpression, Rule::LogicalAND, LOGICAL_AND),
+    (Rule::EqualityExpression, Rule::EqualityOperator, EQUALITY_OPERATOR),
+    (Rule::RelationalExpression, Rule::RelationalOperator, RELATIONAL_OPERATOR),
+    (Rule::BooleanORExpression, Rule::BooleanOR, BOOLEAN_OR),
+    (Rule::BooleanXORExpression, Rule::BooleanXOR, BOOLEAN_XOR),
+    (Rule::BooleanANDExpression, Rule::BooleanAND, BOOLEAN_AND),
+    (Rule::ShiftExpression, Rule::ShiftOperator, SHIFT_OPERATOR),
+    (Rule::AdditiveExpression, Rule::AdditiveOperator, ADDITIVE_OPERATOR),
+    (Rule::MultiplicativeExpression, Rule::MultiplicativeOperator, MULTIPLICATIVE_OPERATOR),
+];
+
+/// The elements of the node being built
+struct Children(Vec<Element>);
+
+impl Children {
+    fn push(&mut self, tree: Tree) {
+        self.0.push(Element::Node(tree));
+    }
+
+    fn skip(&mut self) {
+        self.0.push(Element::Skip);
+    }
+
+    /// `~ tree`
+    fn then(&mut self, tree: Tree) {
+        self.skip();
+        self.push(tree);
+    }
+
+    fn push_optional(&mut self, tree: Option<Tree>) {
+        if let Some(tree) = tree {
+            self.push(tree);
+        }
+    }
+}
+
+type Memo = HashMap<usize, Option<(Tree, usize)>>;
+
+/// Parses a source with `lsv4_root` without pest's parser, giving the pairs pest would.
+///
+/// The parser follows the rules of the grammar one by one, on the tokens of the lexer, and the
+/// binary expressions with a loop per precedence level. Expressions are never parsed twice at the
+/// same place. `None` when the source doesn't parse, or uses something this parser leaves to pest.
+pub(crate) fn parse(source: &str) -> Option<Pairs<'_, Rule>> {
+    let tokens = lex(source);
+    if tokens.iter().any(|token| token.kind == TokenKind::Unterminated) {
+        return None;
+    }
+    let mut parser = Parser {
+        source,
+        tokens: tokens.iter().copied().filter(|token| !token.kind.is_trivia()).collect(),
+        position: 0,
+        bail: false,
+        depth: 0,
+        parent: None,
+        expressions: HashMap::new(),
+        members: HashMap::new(),
+    };
+    let root = parser.root();
+    if parser.bail {
+        return None;
+    }
+    emit::pairs(source, &tokens, &*root?)
+}
+
+struct Parser<'s> {
+    source: &'s str,
+    /// Without the whitespace and the comments
+    tokens: Vec<Token>,
+    position: usize,
+    /// Set when the source may parse differently with pest
+    bail: bool,
+    /// The level of the node being parsed, as `check_depth` counts them
+    depth: usize,
+    /// The rule of the node being parsed
+    parent: Option<Rule>,
+    expressions: Memo,
+    members: Memo,
+}
+
+impl<'s> Parser<'s> {
+    fn token(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn text(&self, token: Token) -> &'s str {
+        &self.source[token.start..token.end]
+    }
+
+    fn at_word(&self, word: &str) -> bool {
+        self.token().is_some_and(|token| token.kind == TokenKind::Word && self.text(token) == word)
+    }
+
+    /// Whether the source continues with `symbol`, made of punctuation characters
+    fn at_symbol(&self, symbol: &str) -> bool {
+        self.token().is_some_and(|token| token.kind == TokenKind::Punct && self.source[token.start..].starts_with(symbol))
+    }
+
+    fn at_identifier(&self) -> bool {
+        self.token().is_some_and(|token| token.kind == TokenKind::Word && !RESERVED.contains(&self.text(token)))
+    }
+
+    /// A node matching `length` bytes, the tokens they are made of being consumed
+    fn leaf(&mut self, rule: Rule, tokens: usize, length: usize) -> Tree {
+        let start = self.tokens[self.position].start;
+        self.position += tokens;
+        Rc::new(Node { rule, children: vec![Element::Text(start, start + length)] })
+    }
+
+    fn keyword(&mut self, rule: Rule, keyword: &str) -> Option<Tree> {
+        self.at_word(keyword).then(|| self.leaf(rule, 1, keyword.len()))
+    }
+
+    /// Every character of a symbol is a token
+    fn symbol(&mut self, rule: Rule, symbol: &str) -> Option<Tree> {
+        self.at_symbol(symbol).then(|| self.leaf(rule, symbol.len(), symbol.len()))
+    }
+
+    fn identifier(&mut self) -> Option<Tree> {
+        self.at_identifier().then(|| {
+            let length = self.tokens[self.position].end - self.tokens[self.position].start;
+            self.leaf(Rule::Identifier, 1, length)
+        })
+    }
+
+    /// The first alternative of an operator rule the source continues with
+    fn operator(&mut self, rule: Rule, operators: &[Operator]) -> Option<Tree> {
+        for operator in operators {
+            let child = match *operator {
+                Operator::Text(text) if self.at_symbol(text) => {
+                    let start = self.tokens[self.position].start;
+                    self.position += text.len();
+                    Element::Text(start, start + text.len())
+                }
+                Operator::Symbol(symbol_rule, symbol) => match self.symbol(symbol_rule, symbol) {
+                    Some(tree) => Element::Node(tree),
+                    None => continue,
+                },
+                Operator::Keyword(keyword_rule, keyword) => match self.keyword(keyword_rule, keyword) {
+                    Some(tree) => Element::Node(tree),
+                    None => continue,
+                },
+                Operator::Text(_) => continue,
+            };
+            return Some(Rc::new(Node { rule, children: vec![child] }));
+        }
+        None
+    }
+
+    /// `parse` the inside of a node of `rule`. `check_depth` reports the trees nested deeper than
+    /// [`MAX_DEPTH`], which an alternative that fails can try to be: the nodes twice as deep are left
+    /// to pest.
+    fn nested<T>(&mut self, rule: Rule, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let level = is_level(rule, self.parent);
+        if self.bail || level && self.depth == 2 * MAX_DEPTH {
+            self.bail = true;
+            return None;
+        }
+        let (depth, parent) = (self.depth, self.parent);
+        self.depth += usize::from(level);
+        self.parent = Some(rule);
+        let result = grow_stack(|| parse(self));
+        (self.depth, self.parent) = (depth, parent);
+        result
+    }
+
+    /// A node of `rule` with the elements `parse` gives, nothing being consumed when it fails
+    fn rule(&mut self, rule: Rule, parse: impl FnOnce(&mut Self, &mut Children) -> Option<()>) -> Option<Tree> {
+        let position = self.position;
+        let mut children = Children(Vec::new());
+        match self.nested(rule, |parser| parse(parser, &mut children)) {
+            Some(()) => Some(Rc::new(Node { rule, children: children.0 })),
+            None => {
+                self.position = position;
+                None
+            }
+        }
+    }
+
+    /// `(...)?`, what `parse` pushed being removed when it fails
+    fn optional(&mut self, children: &mut Children, parse: impl FnOnce(&mut Self, &mut Children) -> Option<()>) -> bool {
+        let (position, length) = (self.position, children.0.len());
+        if parse(self, children).is_some() {
+            return true;
+        }
+        self.position = position;
+        children.0.truncate(length);
+        false
+    }
+
+    /// `(...)*`, the repetitions being separated by skips
+    fn repeat(&mut self, children: &mut Children, mut parse: impl FnMut(&mut Self, &mut Children) -> Option<()>) {
+        if !self.optional(children, &mut parse) {
+            return;
+        }
+        while self.optional(children, |parser, children| {
+            children.skip();
+            parse(parser, children)
+        }) {}
+    }
+
+    /// `Node*`
+    fn repeat_node(&mut self, children: &mut Children, parse: fn(&mut Self) -> Option<Tree>) {
+        self.repeat(children, |parser, children| {
+            children.push(parse(parser)?);
+            Some(())
+        });
+    }
+
+    /// The first of `alternatives` that parses
+    fn choice(&mut self, rule: Rule, alternatives: &[fn(&mut Self) -> Option<Tree>]) -> Option<Tree> {
+        let tree = self.nested(rule, |parser| alternatives.iter().find_map(|alternative| alternative(parser)))?;
+        Some(Rc::new(Node { rule, children: vec![Element::Node(tree)] }))
+    }
+
+    fn memoized(&mut self, memo: fn(&mut Self) -> &mut Memo, parse: fn(&mut Self) -> Option<Tree>) -> Option<Tree> {
+        let position = self.position;
+        if let Some(result) = memo(self).get(&position) {
+            let (tree, end) = result.clone()?;
+            self.position = end;
+            return Some(tree);
+        }
+        let tree = parse(self);
+        let result = tree.clone().map(|tree| (tree, self.position));
+        memo(self).insert(position, result);
+        tree
+    }
+
+    fn root(&mut self) -> Option<Tree> {
+        self.rule(Rule::lsv4_root, |parser, children| {
+            children.skip();
+            parser.repeat_node(children, Self::statement);
+            children.skip();
+            (parser.position == parser.tokens.len()).then_some(())?;
+            children.push(Rc::new(Node { rule: Rule::EOI, children: Vec::new() }));
+            Some(())
+        })
+    }
+
+    // Statements
+
+    fn statement(&mut self) -> Option<Tree> {
+        self.choice(Rule::Statement, &[
+            Self::variable_declaration,
+            Self::function_declaration,
+            Self::class_declaration,
+            Self::enum_declaration,
+            Self::interface_declaration,
+            Self::if_statement,
+            Self::switch_statement,
+            Self::for_statement,
+            Self::while_statement,
+            Self::do_while_statement,
+            Self::block_statement,
+            Self::include_statement,
+            Self::expression_statement,
+            Self::return_statement,
+            Self::break_statement,
+            Self::continue_statement,
+            Self::semi,
+        ])
+    }
+
+    fn semi(&mut self) -> Option<Tree> {
+        self.symbol(Rule::Semi, ";")
+    }
+
+    /// `~ Semi?`
+    fn optional_semi(&mut self, children: &mut Children) {
+        children.skip();
+        let semi = self.semi();
+        children.push_optional(semi);
+    }
+
+    fn include_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::IncludeStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Include, "include")?);
+            children.then(parser.symbol(Rule::LParen, "(")?);
+            children.then(parser.string()?);
+            children.then(parser.symbol(Rule::RParen, ")")?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn return_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::ReturnStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Return, "return")?);
+            children.skip();
+            let expression = parser.expression();
+            children.push_optional(expression);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn break_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::BreakStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Break, "break")?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn continue_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::ContinueStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Continue, "continue")?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn class_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::ClassDeclaration, |parser, children| {
+            children.push(parser.keyword(Rule::Class, "class")?);
+            children.then(parser.identifier()?);
+            children.skip();
+            let extension = parser.rule(Rule::ClassExtension, |parser, children| {
+                children.push(parser.keyword(Rule::Extends, "extends")?);
+                children.then(parser.identifier()?);
+                Some(())
+            });
+            children.push_optional(extension);
+            children.skip();
+            let implementation = parser.rule(Rule::ClassImplementation, |parser, children| {
+                children.push(parser.keyword(Rule::Implements, "implements")?);
+                children.then(parser.interface_list()?);
+                Some(())
+            });
+            children.push_optional(implementation);
+            children.then(parser.rule(Rule::ClassBody, |parser, children| {
+                children.push(parser.symbol(Rule::LBrace, "{")?);
+                children.skip();
+                parser.repeat_node(children, Self::class_statement);
+                children.then(parser.symbol(Rule::RBrace, "}")?);
+                Some(())
+            })?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn class_statement(&mut self) -> Option<Tree> {
+        self.choice(Rule::ClassStatement, &[
+            Self::constructor_declaration,
+            Self::method_declaration,
+            Self::property_declaration,
+            Self::semi,
+        ])
+    }
+
+    fn visibility(&mut self) -> Option<Tree> {
+        self.choice(Rule::Visibility, &[
+            |parser| parser.keyword(Rule::Private, "private"),
+            |parser| parser.keyword(Rule::Public, "public"),
+            |parser| parser.keyword(Rule::Protected, "protected"),
+        ])
+    }
+
+    /// `Visibility? ~ Static?`
+    fn modifiers(&mut self, children: &mut Children) {
+        let visibility = self.visibility();
+        children.push_optional(visibility);
+        children.skip();
+        let static_ = self.keyword(Rule::Static, "static");
+        children.push_optional(static_);
+    }
+
+    /// `(Type ~ &Identifier)?`
+    fn declared_type(&mut self, children: &mut Children) {
+        self.optional(children, |parser, children| {
+            children.push(parser.type_()?);
+            children.skip();
+            parser.at_identifier().then_some(())
+        });
+    }
+
+    fn property_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::PropertyDeclaration, |parser, children| {
+            parser.modifiers(children);
+            children.skip();
+            parser.declared_type(children);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.initializer(children);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn method_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::MethodDeclaration, |parser, children| {
+            parser.modifiers(children);
+            children.skip();
+            parser.declared_type(children);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.parameter_list(children)?;
+            children.skip();
+            let return_type = parser.return_type();
+            children.push_optional(return_type);
+            children.then(parser.block_statement()?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn constructor_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::ConstructorDeclaration, |parser, children| {
+            let visibility = parser.visibility();
+            children.push_optional(visibility);
+            children.then(parser.keyword(Rule::Constructor, "constructor")?);
+            children.skip();
+            parser.parameter_list(children)?;
+            children.then(parser.block_statement()?);
+            Some(())
+        })
+    }
+
+    /// `LParen ~ Parameters? ~ RParen`
+    fn parameter_list(&mut self, children: &mut Children) -> Option<()> {
+        children.push(self.symbol(Rule::LParen, "(")?);
+        children.skip();
+        let parameters = self.parameters();
+        children.push_optional(parameters);
+        children.then(self.symbol(Rule::RParen, ")")?);
+        Some(())
+    }
+
+    /// `(Eq ~ Expression)?`
+    fn initializer(&mut self, children: &mut Children) {
+        self.optional(children, |parser, children| {
+            children.push(parser.symbol(Rule::Eq, "=")?);
+            children.then(parser.expression()?);
+            Some(())
+        });
+    }
+
+    fn interface_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::InterfaceDeclaration, |parser, children| {
+            children.push(parser.keyword(Rule::Interface, "interface")?);
+            children.then(parser.identifier()?);
+            children.skip();
+            let extension = parser.rule(Rule::InterfaceExtension, |parser, children| {
+                children.push(parser.keyword(Rule::Extends, "extends")?);
+                children.then(parser.interface_list()?);
+                Some(())
+            });
+            children.push_optional(extension);
+            children.then(parser.rule(Rule::InterfaceBody, |parser, children| {
+                children.push(parser.symbol(Rule::LBrace, "{")?);
+                children.skip();
+                parser.repeat_node(children, Self::interface_member);
+                children.then(parser.symbol(Rule::RBrace, "}")?);
+                Some(())
+            })?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn interface_list(&mut self) -> Option<Tree> {
+        self.rule(Rule::InterfaceList, |parser, children| {
+            children.push(parser.identifier()?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.symbol(Rule::Comma, ",")?);
+                children.then(parser.identifier()?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    fn interface_member(&mut self) -> Option<Tree> {
+        self.choice(Rule::InterfaceMember, &[Self::method_signature, Self::property_signature, Self::semi])
+    }
+
+    fn method_signature(&mut self) -> Option<Tree> {
+        self.rule(Rule::MethodSignature, |parser, children| {
+            parser.declared_type(children);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.parameter_list(children)?;
+            children.skip();
+            let return_type = parser.return_type();
+            children.push_optional(return_type);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn property_signature(&mut self) -> Option<Tree> {
+        self.rule(Rule::PropertySignature, |parser, children| {
+            parser.declared_type(children);
+            children.then(parser.identifier()?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn enum_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::EnumDeclaration, |parser, children| {
+            children.push(parser.keyword(Rule::Enum, "enum")?);
+            children.then(parser.identifier()?);
+            children.then(parser.symbol(Rule::LBrace, "{")?);
+            children.skip();
+            let members = parser.rule(Rule::EnumMembers, |parser, children| {
+                children.push(parser.enum_member()?);
+                children.skip();
+                parser.repeat(children, |parser, children| {
+                    children.push(parser.symbol(Rule::Comma, ",")?);
+                    children.then(parser.enum_member()?);
+                    Some(())
+                });
+                Some(())
+            });
+            children.push_optional(members);
+            children.then(parser.symbol(Rule::RBrace, "}")?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn enum_member(&mut self) -> Option<Tree> {
+        self.rule(Rule::EnumMember, |parser, children| {
+            children.push(parser.identifier()?);
+            children.skip();
+            parser.initializer(children);
+            Some(())
+        })
+    }
+
+    fn function_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::FunctionDeclaration, |parser, children| {
+            parser.optional(children, |parser, children| {
+                children.push(parser.type_()?);
+                children.skip();
+                parser.at_word("function").then_some(())
+            });
+            children.then(parser.keyword(Rule::Function, "function")?);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.parameter_list(children)?;
+            children.skip();
+            let return_type = parser.return_type();
+            children.push_optional(return_type);
+            children.then(parser.block_statement()?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn variable_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::VariableDeclaration, |parser, children| {
+            let typed = !parser.at_word("var") && !parser.at_word("global");
+            children.push(parser.var_decl_keyword()?);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.initializer(children);
+            children.skip();
+            match parser.semi() {
+                Some(semi) => children.push(semi),
+                None if typed && parser.declaration_continues() => return None,
+                None => {}
+            }
+            Some(())
+        })
+    }
+
+    /// `DeclarationContinuation`
+    fn declaration_continues(&self) -> bool {
+        let symbols = [":", "?", "(", "[", "<<", ">>", "+", "-", "**", "*", "/", "\\", "%", "&", "|", "^", "<", ">", "=", "!"];
+        symbols.iter().any(|symbol| self.at_symbol(symbol))
+            || (self.at_symbol(".") && !self.at_symbol(".."))
+            || ["or", "xor", "and", "instanceof", "in", "as"].iter().any(|keyword| self.at_word(keyword))
+    }
+
+    fn var_decl_keyword(&mut self) -> Option<Tree> {
+        self.rule(Rule::VarDeclKeyword, |parser, children| {
+            if let Some(var_dec) = parser.var_dec() {
+                children.push(var_dec);
+                return Some(());
+            }
+            children.push(parser.keyword(Rule::Global, "global")?);
+            children.skip();
+            parser.declared_type(children);
+            Some(())
+        })
+    }
+
+    fn var_dec(&mut self) -> Option<Tree> {
+        self.choice(Rule::VarDec, &[|parser| parser.keyword(Rule::Var, "var"), Self::type_])
+    }
+
+    /// `Condition`, the expression has to be followed by `)`
+    fn condition(&mut self, children: &mut Children) -> Option<()> {
+        children.push(self.expression()?);
+        children.skip();
+        self.at_symbol(")").then_some(())
+    }
+
+    /// `LParen ~ Condition ~ RParen`
+    fn parenthesized_condition(&mut self, children: &mut Children) -> Option<()> {
+        children.push(self.symbol(Rule::LParen, "(")?);
+        children.skip();
+        self.condition(children)?;
+        children.then(self.symbol(Rule::RParen, ")")?);
+        Some(())
+    }
+
+    fn if_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::IfStatement, |parser, children| {
+            children.push(parser.keyword(Rule::If, "if")?);
+            children.skip();
+            parser.parenthesized_condition(children)?;
+            children.then(parser.statement()?);
+            children.skip();
+            parser.optional(children, |parser, children| {
+                children.push(parser.keyword(Rule::Else, "else")?);
+                children.then(parser.statement()?);
+                Some(())
+            });
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn for_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::ForStatement, |parser, children| {
+            children.push(parser.keyword(Rule::For, "for")?);
+            children.then(parser.symbol(Rule::LParen, "(")?);
+            children.then(parser.choice(Rule::ForInnerCond, &[Self::for_in_cond, Self::for_init_cond_inc])?);
+            children.then(parser.symbol(Rule::RParen, ")")?);
+            children.then(parser.statement()?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    /// `(VarDec ~ &Identifier)?`
+    fn declared_var(&mut self, children: &mut Children) {
+        self.optional(children, |parser, children| {
+            children.push(parser.var_dec()?);
+            children.skip();
+            parser.at_identifier().then_some(())
+        });
+    }
+
+    fn for_in_cond(&mut self) -> Option<Tree> {
+        self.rule(Rule::ForInCond, |parser, children| {
+            parser.optional(children, |parser, children| {
+                parser.declared_var(children);
+                children.then(parser.identifier()?);
+                children.then(parser.symbol(Rule::Colon, ":")?);
+                Some(())
+            });
+            children.skip();
+            parser.declared_var(children);
+            children.then(parser.identifier()?);
+            children.then(parser.keyword(Rule::In, "in")?);
+            children.then(parser.expression()?);
+            Some(())
+        })
+    }
+
+    fn for_init_cond_inc(&mut self) -> Option<Tree> {
+        self.rule(Rule::ForInitCondInc, |parser, children| {
+            let init = parser.choice(Rule::ForInit, &[Self::for_declaration, Self::expression]);
+            children.push_optional(init);
+            children.then(parser.semi()?);
+            children.skip();
+            let condition = parser.expression();
+            children.push_optional(condition);
+            children.then(parser.semi()?);
+            children.skip();
+            let increment = parser.expression();
+            children.push_optional(increment);
+            Some(())
+        })
+    }
+
+    fn for_declaration(&mut self) -> Option<Tree> {
+        self.rule(Rule::ForDeclaration, |parser, children| {
+            children.push(parser.var_decl_keyword()?);
+            children.then(parser.identifier()?);
+            children.skip();
+            parser.initializer(children);
+            Some(())
+        })
+    }
+
+    fn switch_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::SwitchStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Switch, "switch")?);
+            children.skip();
+            parser.parenthesized_condition(children)?;
+            children.then(parser.symbol(Rule::LBrace, "{")?);
+            children.skip();
+            parser.repeat_node(children, |parser| parser.choice(Rule::SwitchClause, &[Self::case_clause, Self::default_clause]));
+            children.then(parser.symbol(Rule::RBrace, "}")?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    /// `~ Statement*`
+    fn statements(&mut self, children: &mut Children) {
+        children.skip();
+        self.repeat_node(children, Self::statement);
+    }
+
+    /// `~ (!(Case | Default) ~ Statement)*`, the statements of a switch clause
+    fn clause_statements(&mut self, children: &mut Children) {
+        children.skip();
+        self.repeat_node(children, |parser| {
+            if parser.at_word("case") || parser.at_word("default") {
+                return None;
+            }
+            parser.statement()
+        });
+    }
+
+    fn case_clause(&mut self) -> Option<Tree> {
+        self.rule(Rule::CaseClause, |parser, children| {
+            children.push(parser.keyword(Rule::Case, "case")?);
+            children.then(parser.expression()?);
+            children.then(parser.symbol(Rule::Colon, ":")?);
+            parser.clause_statements(children);
+            Some(())
+        })
+    }
+
+    fn default_clause(&mut self) -> Option<Tree> {
+        self.rule(Rule::DefaultClause, |parser, children| {
+            children.push(parser.keyword(Rule::Default, "default")?);
+            children.then(parser.symbol(Rule::Colon, ":")?);
+            parser.clause_statements(children);
+            Some(())
+        })
+    }
+
+    fn while_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::WhileStatement, |parser, children| {
+            children.push(parser.keyword(Rule::While, "while")?);
+            children.skip();
+            parser.parenthesized_condition(children)?;
+            children.then(parser.statement()?);
+            Some(())
+        })
+    }
+
+    fn do_while_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::DoWhileStatement, |parser, children| {
+            children.push(parser.keyword(Rule::Do, "do")?);
+            children.then(parser.statement()?);
+            children.then(parser.keyword(Rule::While, "while")?);
+            children.skip();
+            parser.parenthesized_condition(children)?;
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    fn block_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::BlockStatement, |parser, children| {
+            children.push(parser.symbol(Rule::LBrace, "{")?);
+            parser.statements(children);
+            children.then(parser.symbol(Rule::RBrace, "}")?);
+            Some(())
+        })
+    }
+
+    fn expression_statement(&mut self) -> Option<Tree> {
+        self.rule(Rule::ExpressionStatement, |parser, children| {
+            children.push(parser.expression()?);
+            parser.optional_semi(children);
+            Some(())
+        })
+    }
+
+    // Expressions
+
+    fn expression(&mut self) -> Option<Tree> {
+        self.memoized(|parser| &mut parser.expressions, |parser| {
+            parser.choice(Rule::Expression, &[
+                Self::assignment_expression,
+                Self::anonymous_function,
+                Self::lambda_function,
+                Self::ternary_expression,
+            ])
+        })
+    }
+
+    fn assignment_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::AssignmentExpression, |parser, children| {
+            children.push(parser.choice(Rule::LeftHandSideExpression, &[Self::member_expression])?);
+            children.then(parser.operator(Rule::AssignmentOperator, ASSIGNMENT_OPERATOR)?);
+            children.then(parser.expression()?);
+            Some(())
+        })
+    }
+
+    fn anonymous_function(&mut self) -> Option<Tree> {
+        self.rule(Rule::AnonymousFunction, |parser, children| {
+            children.push(parser.keyword(Rule::Function, "function")?);
+            children.skip();
+            parser.parameter_list(children)?;
+            children.skip();
+            let return_type = parser.return_type();
+            children.push_optional(return_type);
+            children.then(parser.block_statement()?);
+            Some(())
+        })
+    }
+
+    fn lambda_function(&mut self) -> Option<Tree> {
+        self.rule(Rule::LambdaFunction, |parser, children| {
+            let parameters = parser.rule(Rule::LambdaParameters, |parser, children| {
+                if let Some(parameter) = parser.parameter() {
+                    children.push(parameter);
+                    return Some(());
+                }
+                parser.parameter_list(children)
+            });
+            children.push_optional(parameters);
+            children.then(parser.operator(Rule::Arrow, ARROW)?);
+            children.skip();
+            parser.optional(children, |parser, children| {
+                children.push(parser.type_()?);
+                children.skip();
+                parser.at_symbol("{").then_some(())
+            });
+            children.then(parser.choice(Rule::LambdaBody, &[Self::block_statement, Self::expression])?);
+            Some(())
+        })
+    }
+
+    fn parameters(&mut self) -> Option<Tree> {
+        self.rule(Rule::Parameters, |parser, children| {
+            children.push(parser.parameter()?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.symbol(Rule::Comma, ",")?);
+                children.then(parser.parameter()?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    fn parameter(&mut self) -> Option<Tree> {
+        self.rule(Rule::Parameter, |parser, children| {
+            let reference = parser.symbol(Rule::Reference, "@");
+            children.push_optional(reference);
+            children.skip();
+            parser.declared_type(children);
+            children.then(parser.identifier()?);
+            Some(())
+        })
+    }
+
+    fn ternary_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::TernaryExpression, |parser, children| {
+            children.push(parser.binary_expression(0)?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.symbol(Rule::QuestionMark, "?")?);
+                children.then(parser.expression()?);
+                children.then(parser.symbol(Rule::Colon, ":")?);
+                children.then(parser.expression()?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    /// The binary expression of `BINARY_LEVELS[level]`, its operands being those of the next level
+    fn binary_expression(&mut self, level: usize) -> Option<Tree> {
+        let Some(&(rule, operator, operators)) = BINARY_LEVELS.get(level) else {
+            return self.power_expression();
+        };
+        self.rule(rule, |parser, children| {
+            children.push(parser.binary_expression(level + 1)?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.operator(operator, operators)?);
+                children.then(parser.binary_expression(level + 1)?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    fn power_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::PowerExpression, |parser, children| {
+            children.push(parser.choice(Rule::UpdateExpression, &[
+                Self::pre_update_expression,
+                Self::post_update_expression,
+                Self::unary_expression,
+            ])?);
+            children.skip();
+            parser.optional(children, |parser, children| {
+                children.push(parser.operator(Rule::PowerOperator, POWER_OPERATOR)?);
+                children.then(parser.power_expression()?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    fn pre_update_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::PreUpdateExpression, |parser, children| {
+            children.push(parser.operator(Rule::UpdateOperator, UPDATE_OPERATOR)?);
+            children.then(parser.member_expression()?);
+            Some(())
+        })
+    }
+
+    fn post_update_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::PostUpdateExpression, |parser, children| {
+            children.push(parser.member_expression()?);
+            children.then(parser.operator(Rule::UpdateOperator, UPDATE_OPERATOR)?);
+            Some(())
+        })
+    }
+
+    fn unary_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::UnaryExpression, |parser, children| {
+            let operator = parser.operator(Rule::UnaryOperator, UNARY_OPERATOR);
+            children.push_optional(operator);
+            children.then(parser.member_expression()?);
+            Some(())
+        })
+    }
+
+    fn member_expression(&mut self) -> Option<Tree> {
+        self.memoized(|parser| &mut parser.members, |parser| {
+            parser.rule(Rule::MemberExpression, |parser, children| {
+                children.push(parser.primary_expression()?);
+                children.skip();
+                parser.repeat_node(children, |parser| parser.choice(Rule::MemberAccessor, &[
+                    Self::array_accessor,
+                    Self::object_accessor,
+                    Self::call_accessor,
+                    Self::nullable_accessor,
+                ]));
+                Some(())
+            })
+        })
+    }
+
+    fn array_accessor(&mut self) -> Option<Tree> {
+        self.rule(Rule::ArrayAccessor, |parser, children| {
+            children.push(parser.symbol(Rule::LSBracket, "[")?);
+            children.then(parser.expression()?);
+            children.then(parser.symbol(Rule::RSBracket, "]")?);
+            Some(())
+        })
+    }
+
+    fn object_accessor(&mut self) -> Option<Tree> {
+        self.rule(Rule::ObjectAccessor, |parser, children| {
+            if parser.at_symbol("..") {
+                return None;
+            }
+            children.push(parser.symbol(Rule::Dot, ".")?);
+            children.then(parser.identifier().or_else(|| parser.keyword(Rule::Class, "class"))?);
+            Some(())
+        })
+    }
+
+    fn call_accessor(&mut self) -> Option<Tree> {
+        self.rule(Rule::CallAccessor, |parser, children| {
+            children.push(parser.symbol(Rule::LParen, "(")?);
+            children.skip();
+            let arguments = parser.rule(Rule::CallArguments, |parser, children| parser.expressions(children));
+            children.push_optional(arguments);
+            children.then(parser.symbol(Rule::RParen, ")")?);
+            Some(())
+        })
+    }
+
+    fn nullable_accessor(&mut self) -> Option<Tree> {
+        if self.at_symbol("!=") {
+            return None;
+        }
+        self.choice(Rule::NullableAccessor, &[|parser| parser.symbol(Rule::ExclamationMark, "!")])
+    }
+
+    /// `Expression ~ (Comma ~ Expression)*`
+    fn expressions(&mut self, children: &mut Children) -> Option<()> {
+        children.push(self.expression()?);
+        children.skip();
+        self.repeat(children, |parser, children| {
+            children.push(parser.symbol(Rule::Comma, ",")?);
+            children.then(parser.expression()?);
+            Some(())
+        });
+        Some(())
+    }
+
+    fn primary_expression(&mut self) -> Option<Tree> {
+        self.choice(Rule::PrimaryExpression, &[
+            |parser| parser.keyword(Rule::This, "this"),
+            |parser| parser.keyword(Rule::Super, "super"),
+            |parser| parser.keyword(Rule::Class, "class"),
+            Self::new_expression,
+            Self::identifier,
+            Self::number,
+            Self::leading_dot_real,
+            Self::string,
+            Self::constant,
+            Self::boolean,
+            |parser| parser.keyword(Rule::Null, "null"),
+            Self::array,
+            Self::set,
+            Self::map,
+            Self::object,
+            Self::interval,
+            Self::parenthesized_expression,
+        ])
+    }
+
+    fn new_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::NewExpression, |parser, children| {
+            children.push(parser.keyword(Rule::New, "new")?);
+            children.then(parser.identifier()?);
+            children.skip();
+            let arguments = parser.call_accessor();
+            children.push_optional(arguments);
+            Some(())
+        })
+    }
+
+    fn number(&mut self) -> Option<Tree> {
+        let token = self.token()?;
+        let TokenKind::Number(kind) = token.kind else {
+            return None;
+        };
+        let rule = match kind {
+            NumberKind::Hex => Rule::Hex,
+            NumberKind::Bin => Rule::Bin,
+            NumberKind::Real => Rule::Real,
+            NumberKind::Integer => Rule::Integer,
+        };
+        let literal = self.leaf(rule, 1, token.end - token.start);
+        Some(Rc::new(Node { rule: Rule::Number, children: vec![Element::Node(literal)] }))
+    }
+
+    /// `.5`, made of several tokens
+    fn leading_dot_real(&mut self) -> Option<Tree> {
+        if !self.at_symbol(".") {
+            return None;
+        }
+        let start = self.tokens[self.position].start;
+        let end = leading_dot_real(self.source, start)?;
+        let tokens = self.tokens[self.position..].iter().take_while(|token| token.end <= end).count();
+        if self.tokens[self.position + tokens - 1].end != end {
+            // The real ends in a token, as in `.5.5`
+            self.bail = true;
+            return None;
+        }
+        let literal = self.leaf(Rule::Real, tokens, end - start);
+        Some(Rc::new(Node { rule: Rule::Number, children: vec![Element::Node(literal)] }))
+    }
+
+    fn string(&mut self) -> Option<Tree> {
+        let token = self.token().filter(|token| token.kind == TokenKind::String)?;
+        let rule = match self.source.as_bytes()[token.start] {
+            b'\'' => Rule::SingleQuoteString,
+            _ => Rule::DoubleQuoteString,
+        };
+        let literal = self.leaf(rule, 1, token.end - token.start);
+        Some(Rc::new(Node { rule: Rule::String, children: vec![Element::Node(literal)] }))
+    }
+
+    fn constant(&mut self) -> Option<Tree> {
+        let token = self.token().filter(|token| token.kind == TokenKind::Other)?;
+        let rule = match self.text(token) {
+            "∞" => Rule::Infinity,
+            "π" => Rule::PI,
+            _ => return None,
+        };
+        let constant = self.leaf(rule, 1, token.end - token.start);
+        Some(Rc::new(Node { rule: Rule::Constant, children: vec![Element::Node(constant)] }))
+    }
+
+    fn boolean(&mut self) -> Option<Tree> {
+        self.choice(Rule::Boolean, &[
+            |parser| parser.keyword(Rule::True, "true"),
+            |parser| parser.keyword(Rule::False, "false"),
+        ])
+    }
+
+    fn array(&mut self) -> Option<Tree> {
+        self.rule(Rule::Array, |parser, children| {
+            children.push(parser.symbol(Rule::LSBracket, "[")?);
+            children.skip();
+            let elements = parser.array_elements();
+            children.push_optional(elements);
+            children.then(parser.symbol(Rule::RSBracket, "]")?);
+            Some(())
+        })
+    }
+
+    fn set(&mut self) -> Option<Tree> {
+        self.rule(Rule::Set, |parser, children| {
+            children.push(parser.symbol(Rule::LChev, "<")?);
+            children.skip();
+            let elements = parser.array_elements();
+            children.push_optional(elements);
+            children.then(parser.symbol(Rule::RChev, ">")?);
+            Some(())
+        })
+    }
+
+    fn array_elements(&mut self) -> Option<Tree> {
+        self.rule(Rule::ArrayElements, |parser, children| parser.expressions(children))
+    }
+
+    fn map(&mut self) -> Option<Tree> {
+        self.rule(Rule::Map, |parser, children| {
+            children.push(parser.symbol(Rule::LSBracket, "[")?);
+            let position = parser.position;
+            match parser.key_value_pairs() {
+                Some(pairs) => children.then(pairs),
+                None => {
+                    parser.position = position;
+                    children.then(parser.symbol(Rule::Colon, ":")?);
+                }
+            }
+            children.then(parser.symbol(Rule::RSBracket, "]")?);
+            Some(())
+        })
+    }
+
+    fn object(&mut self) -> Option<Tree> {
+        self.rule(Rule::Object, |parser, children| {
+            children.push(parser.symbol(Rule::LBrace, "{")?);
+            children.skip();
+            let pairs = parser.key_value_pairs();
+            children.push_optional(pairs);
+            children.then(parser.symbol(Rule::RBrace, "}")?);
+            Some(())
+        })
+    }
+
+    fn key_value_pairs(&mut self) -> Option<Tree> {
+        self.rule(Rule::KeyValuePairs, |parser, children| {
+            children.push(parser.key_value_pair()?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.symbol(Rule::Comma, ",")?);
+                children.then(parser.key_value_pair()?);
+                Some(())
+            });
+            Some(())
+        })
+    }
+
+    fn key_value_pair(&mut self) -> Option<Tree> {
+        self.rule(Rule::KeyValuePair, |parser, children| {
+            children.push(parser.expression()?);
+            children.then(parser.symbol(Rule::Colon, ":")?);
+            children.then(parser.expression()?);
+            Some(())
+        })
+    }
+
+    fn interval(&mut self) -> Option<Tree> {
+        self.rule(Rule::Interval, |parser, children| {
+            children.push(parser.interval_born()?);
+            children.then(parser.expression()?);
+            children.then(parser.symbol(Rule::DotDot, "..")?);
+            children.then(parser.expression()?);
+            children.then(parser.interval_born()?);
+            Some(())
+        })
+    }
+
+    fn interval_born(&mut self) -> Option<Tree> {
+        self.choice(Rule::IntervalBorn, &[
+            |parser| parser.symbol(Rule::LSBracket, "["),
+            |parser| parser.symbol(Rule::RSBracket, "]"),
+        ])
+    }
+
+    fn parenthesized_expression(&mut self) -> Option<Tree> {
+        self.rule(Rule::ParenthesizedExpression, |parser, children| {
+            children.push(parser.symbol(Rule::LParen, "(")?);
+            children.then(parser.expression()?);
+            children.then(parser.symbol(Rule::RParen, ")")?);
+            Some(())
+        })
+    }
+
+    // Types
+
+    fn type_(&mut self) -> Option<Tree> {
+        self.rule(Rule::Type, |parser, children| {
+            children.push(parser.base_type()?);
+            children.skip();
+            parser.repeat(children, |parser, children| {
+                children.push(parser.symbol(Rule::Pipe, "|")?);
+                children.then(parser.base_type()?);
+                Some(())
+            });
+            children.skip();
+            let question_mark = parser.symbol(Rule::QuestionMark, "?");
+            children.push_optional(question_mark);
+            Some(())
+        })
+    }
+
+    fn base_type(&mut self) -> Option<Tree> {
+        self.rule(Rule::BaseType, |parser, children| {
+            if parser.token().is_some_and(|token| VERSIONED_KEYWORDS.contains(&parser.text(token))) {
+                return None;
+            }
+            children.push(parser.identifier()?);
+            children.skip();
+            let sub_type = parser.rule(Rule::SubType, |parser, children| {
+                children.push(parser.symbol(Rule::LChev, "<")?);
+                children.skip();
+                parser.optional(children, |parser, children| {
+                    children.push(parser.type_()?);
+                    children.skip();
+                    parser.repeat(children, |parser, children| {
+                        children.push(parser.symbol(Rule::Comma, ",")?);
+                        children.then(parser.type_()?);
+                        Some(())
+                    });
+                    Some(())
+                });
+                children.skip();
+                let return_type = parser.return_type();
+                children.push_optional(return_type);
+                children.then(parser.symbol(Rule::RChev, ">")?);
+                Some(())
+            });
+            children.push_optional(sub_type);
+            Some(())
+        })
+    }
+
+    fn return_type(&mut self) -> Option<Tree> {
+        self.rule(Rule::ReturnType, |parser, children| {
+            children.push(parser.operator(Rule::Arrow, ARROW)?);
+            children.then(parser.type_()?);
+            Some(())
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use from_pest::FromPest;
+use pest::iterators::Pairs;
+use pest::Parser;
+use crate::ast::lsv4root::Lsv4Root;
+use crate::ast::string::decode_string;
+use crate::lsv4::{Lsv4Parser, Rule};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::validation::validate;
+use crate::check_depth;
+use crate::version::LanguageVersion;
+
+/// An AI split across several files, starting from its entry file
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub root: PathBuf,
+    pub entry: Rc<ProjectFile>,
+    pub diagnostics: Vec<FileDiagnostic>,
+}
+
+/// A parsed file and the files it includes, in the order of its `include`s. A file included by
+/// several others is shared between them.
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// Path relative to the project root, absolute if the entry path is
+    pub path: PathBuf,
+    pub source: String,
+    /// `None` if the file doesn't parse
+    pub ast: Option<Lsv4Root>,
+    pub includes: Vec<Rc<ProjectFile>>,
+}
+
+/// A diagnostic and the file, relative to the project root, it points into
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileDiagnostic {
+    pub path: PathBuf,
+    pub diagnostic: Diagnostic,
+}
+
+/// Parses `entry` and every file it includes, reading them from the disk under `root`
+pub fn resolve(root: &Path, entry: &Path, version: LanguageVersion) -> io::Result<Project> {
+    resolve_with(root, entry, version, |path| std::fs::read_to_string(path))
+}
+
+/// Parses `entry` and every file it includes as `version` code, reading them with `load`.
+///
+/// An include path starting with `/` is relative to the root, any other path is relative to the
+/// folder of the including file. When the path doesn't exist as written, the `.leek` extension
+/// is tried. A file included several times is only parsed once and shared; an include that would close a
+/// cycle is reported and skipped. Only a failure to read `entry` itself is an error, every
+/// other problem ends up in `Project::diagnostics`.
+pub fn resolve_with(root: &Path, entry: &Path, version: LanguageVersion, load: impl FnMut(&Path) -> io::Result<String>) -> io::Result<Project> {
+    let entry = normalize(entry).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "the entry file is outside of the root")
+    })?;
+    let mut resolver = Resolver {
+        root,
+        version,
+        load,
+        stack: Vec::new(),
+        resolved: HashMap::new(),
+        diagnostics: Vec::new(),
+    };
+    let source = (resolver.load)(&root.join(&entry))?;
+    let entry = resolver.resolve_file(entry, source);
+
+    Ok(Project {
+        root: root.to_path_buf(),
+        entry,
+        diagnostics: resolver.diagnostics,
+    })
+}
+
+struct Resolver<'a, F> {
+    root: &'a Path,
+    version: LanguageVersion,
+    load: F,
+    /// The files being resolved, each one including the next
+    stack: Vec<PathBuf>,
+    resolved: HashMap<PathBuf, Rc<ProjectFile>>,
+    diagnostics: Vec<FileDiagnostic>,
+}
+
+impl<F: FnMut(&Path) -> io::Result<String>> Resolver<'_, F> {
+    fn resolve_file(&mut self, path: PathBuf, source: String) -> Rc<ProjectFile> {
+        let mut ast = None;
+        let mut includes = Vec::new();
+        match Lsv4Parser::parse(Rule::lsv4_root, &source) {
+            Err(error) => self.report(&path, Diagnostic::from(error)),
+            Ok(pairs) => match check_depth(&pairs, 0) {
+                Some(diagnostic) => self.report(&path, diagnostic),
+                None => {
+                    for diagnostic in validate(pairs.clone(), self.version) {
+                        self.report(&path, diagnostic);
+                    }
+                    match Lsv4Root::from_pest(&mut pairs.clone()) {
+                        Ok(root) => ast = Some(root),
+                        Err(error) => self.report(&path, Diagnostic::from(error)),
+                    }
+
+                    self.stack.push(path.clone());
+                    for include in find_includes(pairs) {
+                        if let Some(file) = self.resolve_include(&path, include) {
+                            includes.push(file);
+                        }
+                    }
+                    self.stack.pop();
+                }
+            },
+        }
+
+        let file = Rc::new(ProjectFile {
+            path: path.clone(),
+            source,
+            ast,
+            includes,
+        });
+        self.resolved.insert(path, Rc::clone(&file));
+        file
+    }
+
+    fn resolve_include(&mut self, from: &Path, include: Include) -> Option<Rc<ProjectFile>> {
+        let Some(path) = include_path(from, &include.target) else {
+            self.report(from, include.diagnostic(codes::INCLUDE_OUTSIDE_OF_PROJECT, format!("Included file `{}` is outside of the project", include.target)));
+            return None;
+        };
+
+        let mut with_extension = path.clone().into_os_string();
+        with_extension.push(".leek");
+        for candidate in [path, PathBuf::from(with_extension)] {
+            if let Some(position) = self.stack.iter().position(|file| *file == candidate) {
+                let cycle = self.stack[position..].iter()
+                    .chain(std::iter::once(&candidate))
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                self.report(from, include.diagnostic(codes::INCLUDE_CYCLE, format!("Include cycle: {}", cycle)));
+                return None;
+            }
+            if let Some(file) = self.resolved.get(&candidate) {
+                return Some(Rc::clone(file));
+            }
+            if let Ok(source) = (self.load)(&self.root.join(&candidate)) {
+                return Some(self.resolve_file(candidate, source));
+            }
+        }
+
+        self.report(from, include.diagnostic(codes::INCLUDE_NOT_FOUND, format!("Cannot find included file `{}`", include.target)));
+        None
+    }
+
+    fn report(&mut self, path: &Path, diagnostic: Diagnostic) {
+        self.diagnostics.push(FileDiagnostic {
+            path: path.to_path_buf(),
+            diagnostic,
+        });
+    }
+}
+
+/// The path of an `include` and the byte range of its string literal
+struct Include {
+    target: String,
+    start: usize,
+    end: usize,
+}
+
+impl Include {
+    fn diagnostic(&self, code: &'static str, message: String) -> Diagnostic {
+        Diagnostic::error(code, message, self.start, self.end)
+    }
+}
+
+fn find_includes(pairs: Pairs<Rule>) -> Vec<Include> {
+    pairs.flatten()
+        .filter(|pair| pair.as_rule() == Rule::IncludeStatement)
+        .filter_map(|pair| pair.into_inner().find(|inner| inner.as_rule() == Rule::String))
+        .map(|string| Include {
+            target: decode_string(string.as_str()).0,
+            start: string.as_span().start(),
+            end: string.as_span().end(),
+        })
+        .collect()
+}
+
+/// Resolves `target` against the file including it, `None` if it leaves the root
+fn include_path(from: &Path, target: &str) -> Option<PathBuf> {
+    match target.strip_prefix('/') {
+        Some(from_root) => normalize(Path::new(from_root)),
+        None => normalize(&from.parent().unwrap_or(Path::new("")).join(target)),
+    }
+}
+
+/// Removes the `.` and `..` of a path without touching the disk, `None` if it goes above its
+/// starting point
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(_) | Component::RootDir | Component::Prefix(_) => normalized.push(component),
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            Component::CurDir => {}
+        }
+    }
+    Some(normalized)
+}
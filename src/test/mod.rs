@@ -1,8 +1,26 @@
-use from_pest::FromPest;
+use std::any::Any;
+use std::collections::HashSet;
+use from_pest::{ConversionError, FromPest};
 use pest::Parser;
+use crate::ast::{AstNodeMeta, Span, VisitNodes};
+use crate::ast::AstNode;
+use crate::ast::block_statement::{Lbrace, Rbrace};
+use crate::ast::conversion::{required, ConversionFailure};
 use crate::ast::lsv4root::Lsv4Root;
-use crate::lsv4;
-use crate::utils::{PrintAst, PrintProperties};
+use crate::ast::member_expression::{ObjectAccessor, Property};
+use crate::ast::number::{evaluate_number, NumberValue};
+use crate::ast::statement::Statement;
+use crate::ast::string::decode_string;
+use crate::{lsv4, parse, parse_recovering, parser, Backend, ParseOptions};
+use crate::incremental::{reparse, reparse_block, TextEdit};
+use crate::token::{tokenize, TokenKind};
+use crate::line_index::{LineColumn, LineIndex};
+use crate::project::{resolve_with, Project};
+use crate::utils::{PrintAst, PrintProperties, VISITED_PAIRS};
+use crate::diagnostic::{codes, Diagnostic};
+use crate::diagnostic::output::{to_checkstyle, to_json, to_sarif, SourceDiagnostics};
+use crate::validation::validate;
+use crate::version::LanguageVersion;
 
 /// Parses a string and checks if it returns the same string when displayed
 fn symetric_parse(input: &str) {
@@ -12,6 +30,36 @@ fn symetric_parse(input: &str) {
     assert_eq!(input, output);
 }
 
+/// Parses a string with `rule` and returns the text of the significant children of the match
+fn children(rule: lsv4::Rule, input: &str) -> Vec<&str> {
+    let pair = lsv4::Lsv4Parser::parse(rule, input).unwrap_or_else(|e| panic!("{}", e)).next().unwrap();
+    assert_eq!(pair.as_str(), input);
+    pair.into_inner()
+        .filter(|pair| !matches!(pair.as_rule(), lsv4::Rule::WHITESPACE | lsv4::Rule::COMMENT))
+        .map(|pair| pair.as_str().trim_end())
+        .collect()
+}
+
+/// Resolves `entry` from in-memory files, given as `(path, source)`
+fn resolve_files(entry: &str, files: &[(&str, &str)]) -> Project {
+    let files: std::collections::HashMap<_, _> = files.iter()
+        .map(|(path, source)| (std::path::Path::new("/ai").join(path), source.to_string()))
+        .collect();
+    resolve_with(std::path::Path::new("/ai"), std::path::Path::new(entry), LanguageVersion::V4, |path| {
+        files.get(path).cloned().ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))
+    }).unwrap()
+}
+
+/// Parses a string and returns what the validation reports on it
+fn validate_source(input: &str) -> Vec<Diagnostic> {
+    validate_version(input, LanguageVersion::V4)
+}
+
+fn validate_version(input: &str, version: LanguageVersion) -> Vec<Diagnostic> {
+    let pairs = lsv4::Lsv4Parser::parse(lsv4::Rule::lsv4_root, input).unwrap_or_else(|e| panic!("{}", e));
+    validate(pairs, version)
+}
+
 #[test]
 fn empty() {
     symetric_parse("");
@@ -40,4 +88,1074 @@ fn block_statement() {
     symetric_parse("{}");
     symetric_parse("{;}");
     symetric_parse("{\n;\n}");
-}
\ No newline at end of file
+}
+#[test]
+fn expression_statement() {
+    symetric_parse("a;");
+    symetric_parse("a = b;");
+    symetric_parse("a += 1 ;");
+    symetric_parse("a = b = c;");
+    symetric_parse("(a);");
+}
+
+#[test]
+fn binary_expression() {
+    symetric_parse("1 + 2 * 3;");
+    symetric_parse("a || b && c or d and e;");
+    symetric_parse("a ^^ b xor c;");
+    symetric_parse("a | b ^ c & d;");
+    symetric_parse("a == b != c;");
+    symetric_parse("a <= b >= c < d > e;");
+    symetric_parse("a instanceof Array;");
+    symetric_parse("a /* comment */ - b % c;");
+}
+
+#[test]
+fn expression_tree() {
+    use crate::ast::binary_expression::{AdditiveExpression, MultiplicativeExpression};
+    use crate::ast::expression::Expression;
+
+    /// The first node of type `T` in the tree of `source`
+    fn first<T: Clone + 'static>(source: &str) -> T {
+        let mut found = None;
+        parse(source, ParseOptions::default()).unwrap().visit_nodes(&mut |_: &mut AstNodeMeta, data: &mut dyn Any| {
+            found = found.take().or_else(|| data.downcast_ref::<T>().cloned());
+            found.is_none()
+        });
+        found.unwrap_or_else(|| panic!("no {} in {:?}", std::any::type_name::<T>(), source))
+    }
+    // Without the whitespace around the node
+    let print = |node: &dyn PrintAst| node.print_ast(PrintProperties::default()).trim().to_string();
+
+    // `*` binds tighter than `+`, on either side
+    let sum = first::<AdditiveExpression>("a + b * c;");
+    assert_eq!(sum.operands.iter().map(|operand| operand.data.operands.len()).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(print(&sum.operands[1]), "b * c");
+    let sum = first::<AdditiveExpression>("a * b + c;");
+    assert_eq!(sum.operands.iter().map(|operand| operand.data.operands.len()).collect::<Vec<_>>(), [2, 1]);
+    let product = first::<MultiplicativeExpression>("(a + b) * c;");
+    assert_eq!(product.operands.iter().map(|operand| print(operand)).collect::<Vec<_>>(), ["(a + b)", "c"]);
+    // The operators of a level are kept in order, for a left associative reading
+    let difference = first::<AdditiveExpression>("a - b - c;");
+    assert_eq!(difference.operators.iter().map(|operator| print(operator)).collect::<Vec<_>>(), ["-", "-"]);
+    assert_eq!(difference.operands.len(), 3);
+
+    // Assignments and ternaries nest on their right
+    let Expression::Assignment(assignment) = first::<Expression>("a = b = c;") else {
+        panic!("not an assignment");
+    };
+    assert!(matches!(assignment.data.expression.data.as_ref(), Expression::Assignment(_)));
+    let Expression::Ternary(ternary) = first::<Expression>("a ? b : c ? d : e;") else {
+        panic!("not a ternary");
+    };
+    assert_eq!(ternary.data.branches.len(), 1);
+    let Expression::Ternary(alternative) = ternary.data.branches[0].alternative.data.as_ref() else {
+        panic!("not a ternary");
+    };
+    assert_eq!(print(&alternative.data.branches[0].consequent), "d");
+}
+
+#[test]
+fn statement_tree() {
+    let root = parse("if (a) b; else { c; d; }
+while (a) a--;", ParseOptions::default()).unwrap();
+    let [statement, loop_statement] = &root.statements[..] else {
+        panic!("{:?}", root.statements);
+    };
+    let Statement::IfStatement(if_statement) = statement.data.as_ref() else {
+        panic!("{:?}", statement);
+    };
+    assert!(matches!(if_statement.data.statement.data.as_ref(), Statement::ExpressionStatement(_)));
+    let Some(Statement::BlockStatement(block)) = if_statement.data.else_statement.as_ref().map(|node| node.data.as_ref()) else {
+        panic!("{:?}", if_statement.data.else_statement);
+    };
+    assert_eq!(block.data.statements.len(), 2);
+    assert!(block.data.statements.iter().all(|statement| matches!(statement.data.as_ref(), Statement::ExpressionStatement(_))));
+    assert!(matches!(loop_statement.data.as_ref(), Statement::WhileStatement(_)));
+}
+
+#[test]
+fn ternary_expression() {
+    symetric_parse("a ? b : c;");
+    symetric_parse("a ? b : c ? d : e;");
+    symetric_parse("a?b:c;");
+
+    // `a ? b` reads as a nullable type and a name until what follows continues an expression
+    let hand_written = ParseOptions { backend: Backend::HandWritten, ..ParseOptions::default() };
+    for source in ["a ? b + c : d;", "a ? b - c : d;", "a ? b++ : c;", "a ? b-- : c;", "a ? b! : c;"] {
+        symetric_parse(source);
+        assert!(parser::parse(source).is_some(), "{:?} is left to pest", source);
+        assert_eq!(parse(source, hand_written), parse(source, ParseOptions::default()), "{:?}", source);
+    }
+    // A declaration with `var` or `global` ends where the next statement starts
+    for source in ["var x\n++i;", "var x\n-1;", "var x\n!a && f();", "var x\n[1, 2].foo();", "global g\n(f)();"] {
+        let (declaration, statement) = source.split_once('\n').unwrap();
+        assert_eq!(children(lsv4::Rule::lsv4_root, source), [declaration, statement, ""], "{:?}", source);
+        assert!(parser::parse(source).is_some(), "{:?} is left to pest", source);
+        assert_eq!(parse(source, hand_written), parse(source, ParseOptions::default()), "{:?}", source);
+    }
+}
+
+#[test]
+fn unary_and_update_expression() {
+    symetric_parse("-a;");
+    symetric_parse("!a;");
+    symetric_parse("not a;");
+    symetric_parse("typeof a;");
+    symetric_parse("~a;");
+    symetric_parse("++a;");
+    symetric_parse("a--;");
+}
+
+#[test]
+fn member_expression() {
+    symetric_parse("a.b;");
+    symetric_parse("a[1];");
+    symetric_parse("f();");
+    symetric_parse("f(a, b , c);");
+    symetric_parse("a.b[c](d)!.e;");
+    symetric_parse("a . b [ c ] ( d ) ;");
+}
+
+#[test]
+fn primary_expression() {
+    symetric_parse("null;");
+    symetric_parse("true;");
+    symetric_parse("false;");
+    symetric_parse("∞;");
+    symetric_parse("π;");
+    symetric_parse("12;");
+    symetric_parse("'single';");
+    symetric_parse("\"double\";");
+    symetric_parse("[1, 2];");
+}
+
+#[test]
+fn expressions_in_statements() {
+    symetric_parse("return a + b;");
+    symetric_parse("if (a == 1) { b(); } else c();");
+    symetric_parse("while (i < 10) i++;");
+    symetric_parse("do { i--; } while (i > 0);");
+    symetric_parse("var a = 1 + 2;");
+}
+
+#[test]
+fn type_annotation() {
+    symetric_parse("integer a;");
+    symetric_parse("integer? a = null;");
+    symetric_parse("integer | real b = 1;");
+    symetric_parse("integer|string ? c;");
+    symetric_parse("Array<integer> d = [];");
+    symetric_parse("Array< Array<integer> > e;");
+    symetric_parse("Array<Array<integer>> e;");
+    symetric_parse("Map<string, integer> f;");
+    symetric_parse("Function<integer => void> g;");
+    symetric_parse("Function<integer, real -> boolean> h;");
+    symetric_parse("Function< => void> i;");
+    symetric_parse("global integer j = 1;");
+    symetric_parse("function f(integer a, Array<real>? b) {}");
+}
+
+#[test]
+fn class_declaration() {
+    symetric_parse("class A {}");
+    symetric_parse("class A extends B {};");
+    symetric_parse("class A { x }");
+    symetric_parse("class A {\n    integer x = 1;\n    private static real y;\n    public z = [];\n}");
+    symetric_parse("class A { constructor() {} }");
+    symetric_parse("class A { public constructor(integer a, b) { this.a = a; } }");
+    symetric_parse("class A { m() {} static integer n(x) { return x; } protected Array<integer> o() {} ; }");
+}
+
+#[test]
+fn for_statement() {
+    symetric_parse("for (;;) {}");
+    symetric_parse("for (var i = 0; i < 10; i++) {}");
+    symetric_parse("for (integer i = 0 ; i < 10 ; i += 2) ;");
+    symetric_parse("for (i = 0; i < 10; ++i) a[i] = i;");
+    symetric_parse("for (var x in [1, 2]) { x; }");
+    symetric_parse("for (x in xs) {};");
+    symetric_parse("for (var k : var v in m) {}");
+    symetric_parse("for (integer k : string v in m) {}");
+    symetric_parse("for ( k /* key */ : v in m ) {}");
+}
+
+#[test]
+fn anonymous_function() {
+    symetric_parse("var f = function() {};");
+    symetric_parse("var f = function (a, integer b) { return a + b; };");
+    symetric_parse("var f = function(x) => integer { return x; };");
+    symetric_parse("(function() {})();");
+}
+
+#[test]
+fn lambda_function() {
+    symetric_parse("var f = x => x * 2;");
+    symetric_parse("var f = x -> x;");
+    symetric_parse("var f = () => 1;");
+    symetric_parse("var f = => 1;");
+    symetric_parse("var f = (a, b) => a + b;");
+    symetric_parse("var f = (integer a) => integer { return a; };");
+    symetric_parse("var f = x => { x++; };");
+    symetric_parse("var f = x => f(x);");
+    symetric_parse("var f = x => a[x];");
+    symetric_parse("arrayMap([1, 2], x => x + 1);");
+}
+
+#[test]
+fn array_and_set() {
+    symetric_parse("[];");
+    symetric_parse("[1, 2 , 3];");
+    symetric_parse("[ [1], [] ];");
+    symetric_parse("<>;");
+    symetric_parse("<1, 2>;");
+}
+
+#[test]
+fn map_and_object() {
+    symetric_parse("[:];");
+    symetric_parse("[ : ];");
+    symetric_parse("['a': 1, 'b' : 2];");
+    symetric_parse("var o = {};");
+    symetric_parse("var o = {a: 1, b: [1, 2]};");
+    symetric_parse("var o = { a : { b : 1 } };");
+}
+
+#[test]
+fn interval() {
+    symetric_parse("[1..2];");
+    symetric_parse("]1..2[;");
+    symetric_parse("[1 .. 2[;");
+    symetric_parse("]a..b + 1];");
+    symetric_parse("[1.5..2.5];");
+}
+
+#[test]
+fn string_literal() {
+    symetric_parse("'a\\'b';");
+    symetric_parse("\"a\\\"b\";");
+    symetric_parse("\"\\\\\";");
+    symetric_parse("'\\n\\t\\u00e9';");
+    symetric_parse("'\\q';");
+}
+
+#[test]
+fn string_value() {
+    assert_eq!(decode_string("'a\\'b'").0, "a'b");
+    assert_eq!(decode_string("\"a\\\"b\"").0, "a\"b");
+    assert_eq!(decode_string("\"\\\\\"").0, "\\");
+    assert_eq!(decode_string("'\\n\\t\\r'").0, "\n\t\r");
+    assert_eq!(decode_string("'\\u00e9'").0, "é");
+    assert_eq!(decode_string("'\\uD83D\\uDE00'").0, "😀");
+    assert_eq!(decode_string("'\\q'").0, "\\q");
+}
+
+#[test]
+fn invalid_escape() {
+    assert_eq!(validate_source("'ok \\n';"), vec![]);
+    let diagnostics = validate_source("var s = 'a\\qb';");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].start, diagnostics[0].end), (10, 12));
+    let diagnostics = validate_source("'\\u12' + '\\uD800';");
+    assert_eq!(diagnostics.iter().map(|d| (d.start, d.end)).collect::<Vec<_>>(), vec![(1, 5), (10, 16)]);
+}
+
+#[test]
+fn number_literal() {
+    symetric_parse("1_000_000;");
+    symetric_parse("0xFF + 0Xff;");
+    symetric_parse("0b1010 + 0B1;");
+    symetric_parse("1.5 + .5 + 1.;");
+    symetric_parse("1e10 + 1.5E-3 + 2e+2;");
+    symetric_parse("[1..2];");
+}
+
+#[test]
+fn number_value() {
+    assert_eq!(evaluate_number("1_000").0, Some(NumberValue::Integer(1000)));
+    assert_eq!(evaluate_number("0x1F").0, Some(NumberValue::Integer(31)));
+    assert_eq!(evaluate_number("0b101").0, Some(NumberValue::Integer(5)));
+    assert_eq!(evaluate_number("0xFFFFFFFFFFFFFFFF").0, Some(NumberValue::Integer(-1)));
+    assert_eq!(evaluate_number("1.5").0, Some(NumberValue::Real(1.5)));
+    assert_eq!(evaluate_number(".5").0, Some(NumberValue::Real(0.5)));
+    assert_eq!(evaluate_number("1.5e3").0, Some(NumberValue::Real(1500.0)));
+    assert_eq!(evaluate_number("2E-1").0, Some(NumberValue::Real(0.2)));
+    assert_eq!(evaluate_number("9223372036854775808").0, None);
+    assert_eq!(evaluate_number("1e400").0, None);
+}
+
+#[test]
+fn invalid_number() {
+    let spans = |input: &str| validate_source(input).iter().map(|d| (d.start, d.end)).collect::<Vec<_>>();
+    assert_eq!(spans("1_000 + 0x_1F + 1.5e3;"), vec![(10, 11)]);
+    assert_eq!(spans("1__0 + 1_;"), vec![(1, 3), (8, 9)]);
+    assert_eq!(spans("0b102;"), vec![(4, 5)]);
+    assert_eq!(spans("0x;"), vec![(0, 2)]);
+    assert_eq!(spans("12px;"), vec![(2, 3)]);
+    assert_eq!(spans("1e;"), vec![(0, 2)]);
+    assert_eq!(spans("99999999999999999999;"), vec![(0, 20)]);
+    assert_eq!(spans("1e999;"), vec![(0, 5)]);
+}
+
+#[test]
+fn operators() {
+    symetric_parse("a << 1 >> 2 >>> 3;");
+    symetric_parse("a ** b ** c;");
+    symetric_parse("a \\ b;");
+    symetric_parse("a === b !== c;");
+    symetric_parse("a **= 2; a \\= 2; a &= 1; a |= 1; a ^= 1; a <<= 1; a >>= 1; a >>>= 1;");
+    symetric_parse("var x = a & b | c ^ d;");
+
+    // `a ? b` followed by any of them is a ternary, not a declaration of `b`
+    let v3 = ParseOptions { version: LanguageVersion::V3, ..ParseOptions::default() };
+    for operator in ["**", "<<", ">>", ">>>", "\\", "===", "!=="] {
+        let source = format!("a ? b {operator} 2 : c;");
+        symetric_parse(&source);
+        assert!(parser::parse(&source).is_some(), "{:?} is left to pest", source);
+        assert!(parse(&source, ParseOptions { backend: Backend::HandWritten, ..v3 }).is_ok(), "{:?}", source);
+    }
+}
+
+#[test]
+fn operator_associativity() {
+    use lsv4::Rule;
+    assert_eq!(children(Rule::PowerExpression, "a ** b ** c"), vec!["a", "**", "b ** c"]);
+    assert_eq!(children(Rule::PowerExpression, "-a ** 2"), vec!["-a", "**", "2"]);
+    assert_eq!(children(Rule::MultiplicativeExpression, "a \\ b ** c * d"), vec!["a", "\\", "b ** c", "*", "d"]);
+    assert_eq!(children(Rule::ShiftExpression, "a << b >>> c >> d"), vec!["a", "<<", "b", ">>>", "c", ">>", "d"]);
+    assert_eq!(children(Rule::ShiftExpression, "a + b << c - d"), vec!["a + b", "<<", "c - d"]);
+    assert_eq!(children(Rule::RelationalExpression, "a << 1 < b"), vec!["a << 1", "<", "b"]);
+    assert_eq!(children(Rule::EqualityExpression, "a === b == c !== d"), vec!["a", "===", "b", "==", "c", "!==", "d"]);
+    assert_eq!(children(Rule::AssignmentExpression, "a **= b <<= c"), vec!["a", "**=", "b <<= c"]);
+}
+
+#[test]
+fn switch_statement() {
+    symetric_parse("switch (a) {}");
+    symetric_parse("switch (a) { case 1: b(); break; case 2: case 3: c(); default: d(); }");
+    symetric_parse("switch (a + 1) {\n    case 'x': { b(); }\n    default:\n}");
+    symetric_parse("switch(a){case b?1:2:break;};");
+}
+
+#[test]
+fn enum_declaration() {
+    symetric_parse("enum Empty {}");
+    symetric_parse("enum Direction { UP, DOWN, LEFT, RIGHT }");
+    symetric_parse("enum Flag { A = 1, B = 1 << 1 /* two */, C = A | B };");
+}
+
+#[test]
+fn interface_declaration() {
+    symetric_parse("interface Empty {}");
+    symetric_parse("interface Shape extends Named, Printable { real area(); integer sides; name; draw(x, y) }");
+    symetric_parse("class Square extends Rectangle implements Shape, Comparable { }");
+    symetric_parse("class A implements I {}");
+}
+
+#[test]
+fn include_statement() {
+    symetric_parse("include('lib/utils');");
+    symetric_parse("include ( \"a\" )\nvar x = 1;");
+    symetric_parse("include(path);");
+}
+
+#[test]
+fn include_resolution() {
+    let project = resolve_files("main.leek", &[
+        ("main.leek", "include('lib/a'); include(\"lib/b.leek\");"),
+        ("lib/a.leek", "include('b'); var a = 1;"),
+        ("lib/b.leek", "include('/util'); var b = 2;"),
+        ("util", "var u = 3;"),
+    ]);
+    assert_eq!(project.diagnostics, vec![]);
+    let a = &project.entry.includes[0];
+    assert_eq!(a.path, std::path::Path::new("lib/a.leek"));
+    assert_eq!(a.includes[0].path, std::path::Path::new("lib/b.leek"));
+    assert_eq!(a.includes[0].includes[0].path, std::path::Path::new("util"));
+    assert_eq!(project.entry.includes[1].path, std::path::Path::new("lib/b.leek"));
+    assert!(project.entry.includes[1].ast.is_some());
+    assert!(std::rc::Rc::ptr_eq(&a.includes[0], &project.entry.includes[1]));
+
+    // Each file includes the next one twice, which would be 2^40 files if they weren't shared
+    let sources = (0..40).map(|i| (format!("f{i}"), format!("include('f{}'); include('f{}');", i + 1, i + 1))).collect::<Vec<_>>();
+    let mut files = sources.iter().map(|(path, source)| (path.as_str(), source.as_str())).collect::<Vec<_>>();
+    files.push(("f40", "var last = 1;"));
+    let project = resolve_files("f0", &files);
+    assert_eq!(project.diagnostics, vec![]);
+    let mut file = &project.entry;
+    while let [first, second] = &file.includes[..] {
+        assert!(std::rc::Rc::ptr_eq(first, second));
+        file = first;
+    }
+    assert_eq!(file.path, std::path::Path::new("f40"));
+
+    // An absolute entry path stays absolute
+    let project = resolve_files("/ai/main.leek", &[("main.leek", "include('lib/a');"), ("lib/a.leek", "var a = 1;")]);
+    assert_eq!(project.diagnostics, vec![]);
+    assert_eq!(project.entry.path, std::path::Path::new("/ai/main.leek"));
+    assert_eq!(project.entry.includes[0].path, std::path::Path::new("/ai/lib/a.leek"));
+}
+
+#[test]
+fn include_errors() {
+    let project = resolve_files("main", &[
+        ("main", "include('a');\ninclude('missing');\ninclude('../outside');\ninclude('broken');"),
+        ("a", "include('b');"),
+        ("b", "var b = 1;\ninclude('a');"),
+        ("broken", "var = ;"),
+    ]);
+    let diagnostics = project.diagnostics.iter()
+        .map(|d| (d.path.to_str().unwrap(), d.diagnostic.start, d.diagnostic.end, d.diagnostic.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics, vec![
+        ("b", 19, 22, "Include cycle: a -> b -> a"),
+        ("main", 22, 31, "Cannot find included file `missing`"),
+        ("main", 42, 54, "Included file `../outside` is outside of the project"),
+        ("broken", 4, 4, "expected Identifier, WHITESPACE, or COMMENT"),
+    ]);
+    assert!(project.entry.includes[1].ast.is_none());
+}
+
+#[test]
+fn return_type() {
+    symetric_parse("function f() => integer { return 1; }");
+    symetric_parse("function f(integer a, b) -> Array<integer> {}");
+    symetric_parse("integer function f() {}");
+    symetric_parse("integer | null function f() {}");
+    symetric_parse("class A { f() => real { return 1.5; } static string g() {} }");
+    symetric_parse("interface I { count() => integer; }");
+    assert_eq!(validate_source("function f() => integer {}"), vec![]);
+    let diagnostics = validate_source("integer function f() => integer {}");
+    assert_eq!(diagnostics.iter().map(|d| (d.start, d.end)).collect::<Vec<_>>(), vec![(21, 31)]);
+}
+
+#[test]
+fn class_references() {
+    symetric_parse("class A extends B { constructor(x) { super(x); this.x = x; class.count++; } }");
+    symetric_parse("var a = new A(1, 2).b; var c = new C;");
+    symetric_parse("new A ( ) ;");
+    symetric_parse("A.staticField = super.f();");
+    assert_eq!(validate_source("class A { f() { return () => this.x + class.y + super.z(); } }"), vec![]);
+    let diagnostics = validate_source("this.x = 1;\nfunction f() { return super.g() + class.h; }\nclass A {}");
+    assert_eq!(diagnostics.iter().map(|d| (d.start, d.end, d.message.as_str())).collect::<Vec<_>>(), vec![
+        (0, 4, "`this` can only be used inside a class"),
+        (34, 39, "`super` can only be used inside a class"),
+        (46, 51, "`class` can only be used inside a class"),
+    ]);
+
+    // `class` is also a property
+    for source in ["var name = this.class.name;", "obj.class = A;", "a!.class;"] {
+        symetric_parse(source);
+        assert!(parser::parse(source).is_some(), "{:?} is left to pest", source);
+    }
+    assert_eq!(validate_source("var c = obj.class;"), vec![]);
+    let mut properties = Vec::new();
+    parse("obj.class;", ParseOptions::default()).unwrap().visit_nodes(&mut |_: &mut AstNodeMeta, data: &mut dyn Any| {
+        properties.extend(data.downcast_ref::<ObjectAccessor>().map(|accessor| accessor.property.clone()));
+        true
+    });
+    assert!(matches!(properties[..], [Property::Class(_)]), "{:?}", properties);
+}
+
+#[test]
+fn language_versions() {
+    symetric_parse("function f(@a, @b) { var c = @a; }");
+    let messages = |input: &str, version| validate_version(input, version).into_iter()
+        .map(|d| (d.start, d.end, d.message))
+        .collect::<Vec<_>>();
+    assert_eq!(messages("var a = @b; a === 1;", LanguageVersion::V1), vec![]);
+    assert_eq!(messages("var a = @b; a === 1;", LanguageVersion::V4), vec![
+        (8, 9, String::from("The `@` reference is not available in LeekScript 4, only in LeekScript 1 to 3")),
+        (14, 17, String::from("Strict equality is not available in LeekScript 4, only in LeekScript 1 to 3")),
+    ]);
+    assert_eq!(messages("class A { constructor() { this.a = {}; } }", LanguageVersion::V1), vec![
+        (0, 5, String::from("The class declaration is not available in LeekScript 1, only in LeekScript 2 to 4")),
+        (26, 30, String::from("`this` is not available in LeekScript 1, only in LeekScript 2 to 4")),
+        (35, 37, String::from("The object literal is not available in LeekScript 1, only in LeekScript 2 to 4")),
+    ]);
+    assert_eq!(messages("Array<integer> a = [1..2]; function f(integer x) => integer {}", LanguageVersion::V3), vec![
+        (0, 14, String::from("The type annotation is not available in LeekScript 3, only in LeekScript 4")),
+        (19, 25, String::from("The interval is not available in LeekScript 3, only in LeekScript 4")),
+        (38, 45, String::from("The type annotation is not available in LeekScript 3, only in LeekScript 4")),
+        (52, 59, String::from("The type annotation is not available in LeekScript 3, only in LeekScript 4")),
+    ]);
+    assert_eq!(messages("switch (a) {} var b = new A;", LanguageVersion::V2).len(), 1);
+}
+
+#[test]
+fn versioned_keywords() {
+    // `this`, `super` and `new` are keywords from LeekScript 2, the others from LeekScript 4
+    let v2 = "global this = 1; var super = [this]; function g(new) { for (x in super) { new(x); } }";
+    let v4 = "var default = 1; var enum = 2; var switch = 1; var interface = 3; var case = default + enum;\n\
+        function f(implements, default) { return implements(default); }";
+    let reserved = |source, version, backend| match parse(source, ParseOptions { version, backend }) {
+        Ok(root) => {
+            assert_eq!(root.print_ast(PrintProperties::default()), source);
+            0
+        }
+        Err(errors) => {
+            let count = errors.diagnostics.iter().filter(|d| d.code == codes::RESERVED_NAME).count();
+            assert!(count > 0, "{}", errors);
+            count
+        }
+    };
+    for backend in [Backend::Pest, Backend::HandWritten] {
+        for source in [v2, v4] {
+            assert!(parser::parse(source).is_some(), "{:?} is left to pest", source);
+        }
+        assert_eq!(reserved(v2, LanguageVersion::V1, backend), 0);
+        assert_eq!(reserved(v2, LanguageVersion::V2, backend), 4);
+        for version in [LanguageVersion::V1, LanguageVersion::V2, LanguageVersion::V3] {
+            assert_eq!(reserved(v4, version, backend), 0);
+        }
+        assert_eq!(reserved(v4, LanguageVersion::V4, backend), 11);
+    }
+
+    // The keywords keep their meaning where the construct parses
+    let messages = |input: &str, version| validate_version(input, version).into_iter()
+        .map(|d| (d.start, d.end, d.message))
+        .collect::<Vec<_>>();
+    assert_eq!(messages("var a = new A(); switch (a) { case 1: default: a; }", LanguageVersion::V4), vec![]);
+    assert_eq!(messages("var new = 1; new;", LanguageVersion::V4), vec![
+        (4, 7, String::from("`new` is a keyword in LeekScript 4 and can't be used as a name")),
+        (13, 16, String::from("`new` is a keyword in LeekScript 4 and can't be used as a name")),
+    ]);
+    assert_eq!(messages("var x = this.a;", LanguageVersion::V1), vec![]);
+    symetric_parse("enum E { A } interface I {} class A implements I {} switch (a) { case 1: b default: c }");
+}
+
+#[test]
+fn parse_entry_point() {
+    let root = parse("var a = 1; // end", ParseOptions::default()).unwrap();
+    assert_eq!(root.print_ast(PrintProperties::default()), "var a = 1; // end");
+    let errors = parse("var a = ;", ParseOptions::default()).unwrap_err();
+    assert_eq!((errors.diagnostics.len(), errors.diagnostics[0].start), (1, 8));
+    assert_eq!(parse("var a = @b;", ParseOptions::default()).unwrap_err().diagnostics.len(), 1);
+    assert!(parse("var a = @b;", ParseOptions { version: LanguageVersion::V1, ..Default::default() }).is_ok());
+}
+
+#[test]
+fn truncated_input() {
+    let source = "include('lib'); class A extends B implements I { constructor(x) { this.x = [1..2] + 0x1F ** -2; } }\n\
+        function f(integer a) => string { switch (a) { case 1: return 'a\\u00e9'; default: return \"\"; } }\n\
+        var m = [1: {a: <1, 2>}]; enum E { A = 1_000, B } /* é */";
+    parse(source, ParseOptions::default()).unwrap();
+    for end in (0..source.len()).filter(|end| source.is_char_boundary(*end)) {
+        let recovered = parse_recovering(&source[..end], ParseOptions::default());
+        let root = recovered.root.unwrap_or_else(|| panic!("{:?}", recovered.diagnostics));
+        assert_eq!(root.print_ast(PrintProperties::default()), &source[..end]);
+    }
+}
+
+#[test]
+fn deep_nesting() {
+    let sources = [
+        format!("{}{}", "{".repeat(150), "}".repeat(150)),
+        format!("{}a;", "if (a) ".repeat(150)),
+        format!("f = {}1;", "x => ".repeat(150)),
+        format!("{}2;", "a ** ".repeat(300)),
+        // Deep enough for pest to run out of stack first
+        format!("{}{}", "{".repeat(1000), "}".repeat(1000)),
+        format!("f = {}1;", "x => ".repeat(1000)),
+    ];
+    let hand_written = ParseOptions { backend: Backend::HandWritten, ..ParseOptions::default() };
+    for source in &sources {
+        // The hand-written parser leaves to pest what it would nest twice too deeply
+        assert!(parser::parse(source).is_none());
+        for options in [ParseOptions::default(), hand_written] {
+            let errors = parse(source, options).unwrap_err();
+            assert_eq!(errors.diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(), [codes::TOO_DEEP], "{}", errors);
+        }
+        let recovered = parse_recovering(source, ParseOptions::default());
+        assert!(recovered.diagnostics.iter().any(|d| d.code == codes::TOO_DEEP));
+        // When pest gives up first, the recovering parser skips what it couldn't parse
+        if let Some(root) = recovered.root {
+            assert_eq!(&root.print_ast(PrintProperties::default()), source);
+        }
+    }
+    // Each statement, expression and type is a level
+    for (depth, parses) in [(64, true), (65, false)] {
+        let source = format!("{}{}", "{".repeat(depth), "}".repeat(depth));
+        assert_eq!(parse(&source, ParseOptions::default()).is_ok(), parses, "{}", depth);
+        assert_eq!(parse(&source, hand_written).is_ok(), parses, "{}", depth);
+        assert!(parser::parse(&source).is_some());
+    }
+    // pest takes seconds on a dozen levels of these, the hand-written parser gives the same pairs
+    let sources = [12, 60].into_iter().flat_map(|depth| {
+        [
+            format!("var a = {}1{};", "f(".repeat(depth), ")".repeat(depth)),
+            format!("var a = {}1{};", "[".repeat(depth), "]".repeat(depth)),
+            format!("x = {}1{};", "(".repeat(depth), ")".repeat(depth)),
+        ]
+    });
+    for source in sources {
+        assert!(parser::parse(&source).is_some());
+        assert_eq!(parse(&source, hand_written).unwrap().print_ast(PrintProperties::default()), source);
+    }
+    // Too deep, but not so deep that the hand-written parser leaves it to pest
+    let source = format!("x = {}1{};", "(".repeat(100), ")".repeat(100));
+    assert!(parser::parse(&source).is_some());
+    let errors = parse(&source, hand_written).unwrap_err();
+    assert_eq!(errors.diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(), [codes::TOO_DEEP], "{}", errors);
+}
+
+#[test]
+fn conversion_failure() {
+    let mut pairs = lsv4::Lsv4Parser::parse(lsv4::Rule::Statement, "while (x) {}  ").unwrap();
+    let statement = pairs.peek().unwrap();
+    let failure = ConversionFailure::at(&statement, "Statement");
+    assert_eq!((failure.rule, failure.start, failure.end), (lsv4::Rule::WhileStatement, 0, 12));
+    assert_eq!(Lsv4Root::from_pest(&mut pairs), Err(ConversionError::NoMatch));
+
+    // A node missing from its parent is reported at the pair found in its place
+    let block = lsv4::Lsv4Parser::parse(lsv4::Rule::BlockStatement, "{ a; } ").unwrap().next().unwrap();
+    let mut inner = block.clone().into_inner();
+    AstNode::<Lbrace>::from_pest(&mut inner).unwrap();
+    let missing = required::<AstNode<Rbrace>>(&block, &mut inner).unwrap_err();
+    assert_eq!(missing, ConversionError::Malformed(ConversionFailure {
+        rule: lsv4::Rule::ExpressionStatement,
+        start: 2,
+        end: 4,
+        expected: "Rbrace",
+    }));
+
+    let diagnostic = Diagnostic::from(ConversionError::Malformed(failure));
+    assert_eq!(diagnostic.message, "Could not build the syntax tree: expected a `Statement` node, found `WhileStatement`");
+    assert_eq!((diagnostic.start, diagnostic.end), (0, 12));
+}
+
+#[test]
+fn error_recovery() {
+    let source = "var a = ;\nvar b = 2;\nif (x) }\nfunction f() { g(1 +); return @; }\nwhile (#) {}";
+    let recovered = parse_recovering(source, ParseOptions::default());
+    let diagnostics: Vec<_> = recovered.diagnostics.iter()
+        .map(|diagnostic| (diagnostic.start, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(diagnostics, [
+        (8, "expected Expression, WHITESPACE, or COMMENT"),
+        (28, "Missing statement"),
+        (28, "Invalid statement"),
+        (46, "Invalid statement"),
+        (60, "Invalid statement"),
+        (72, "Invalid expression"),
+    ]);
+
+    let root = recovered.root.unwrap();
+    assert_eq!(root.print_ast(PrintProperties::default()), source);
+    let kinds: Vec<_> = root.statements.iter()
+        .map(|statement| match statement.data.as_ref() {
+            Statement::VariableDeclaration(_) => "var",
+            Statement::IfStatement(_) => "if",
+            Statement::FunctionDeclaration(_) => "function",
+            Statement::WhileStatement(_) => "while",
+            Statement::Error(_) => "error",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(kinds, ["var", "var", "if", "error", "function", "while"]);
+
+    let valid = "var a = 1; if (a) { a++ } // end";
+    let recovered = parse_recovering(valid, ParseOptions::default());
+    assert!(recovered.diagnostics.is_empty());
+    assert_eq!(recovered.root, Some(parse(valid, ParseOptions::default()).unwrap()));
+}
+
+#[test]
+fn spans() {
+    let source = "var a = 1; // one\nif (a) { a++ }\n";
+    let root = parse(source, ParseOptions::default()).unwrap();
+    let spans: Vec<_> = root.statements.iter().map(|statement| &source[statement.span().start..statement.span().end]).collect();
+    assert_eq!(spans, ["var a = 1;", "if (a) { a++ }"]);
+    let Statement::IfStatement(if_statement) = root.statements[1].data.as_ref() else {
+        panic!("{:?}", root.statements[1]);
+    };
+    assert_eq!((if_statement.span().start, if_statement.span().end), (18, 32));
+    assert_eq!((if_statement.data.if_keyword.span().start, if_statement.data.if_keyword.span().end), (18, 20));
+    assert_eq!((if_statement.data.expression.span().start, if_statement.data.expression.span().end), (22, 23));
+    assert_eq!(root.eoi.span(), Span { start: 33, end: 33 });
+
+    let recovered = parse_recovering("var a = ;", ParseOptions::default()).root.unwrap();
+    let Statement::VariableDeclaration(declaration) = recovered.statements[0].data.as_ref() else {
+        panic!("{:?}", recovered.statements[0]);
+    };
+    assert_eq!(declaration.data.expression.as_ref().map(|expression| expression.span()), Some(Span { start: 8, end: 8 }));
+}
+
+#[test]
+fn linear_conversion() {
+    // The rows of a table, as statements and as the elements of a single literal
+    let statements = |rows: usize| (0..rows).map(|row| format!("var row{row} = [{row}, 'name', {row}.5]; // row\n")).collect::<String>();
+    let elements = |rows: usize| format!("global TABLE = [{}];", (0..rows).map(|row| format!("\n  [{row}, 'name', {row}.5]")).collect::<Vec<_>>().join(","));
+    for generate in [&statements as &dyn Fn(usize) -> String, &elements] {
+        let [visited, four_times] = [500, 2000].map(|rows| {
+            let source = generate(rows);
+            let pairs = parser::parse(&source).unwrap();
+            VISITED_PAIRS.with(|visited| visited.set(0));
+            Lsv4Root::from_pest(&mut pairs.clone()).unwrap();
+            VISITED_PAIRS.with(|visited| visited.get())
+        });
+        // 4 times as many for a linear conversion, 16 for a quadratic one
+        assert!(four_times <= 4 * visited + 100, "{} pairs visited for 500 rows, {} for 2000", visited, four_times);
+    }
+}
+
+#[test]
+fn line_index() {
+    let index = LineIndex::new("a\r\nbé😀c\rd\n");
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line_column(10), LineColumn { line: 1, column: 7 });
+    assert_eq!(index.line_column_utf16(10), LineColumn { line: 1, column: 4 });
+    assert_eq!(index.line_column(12), LineColumn { line: 2, column: 0 });
+    assert_eq!(index.line_column(100), LineColumn { line: 3, column: 0 });
+    assert_eq!(index.offset(LineColumn { line: 0, column: 1 }), Some(1));
+    assert_eq!(index.offset(LineColumn { line: 0, column: 2 }), None);
+    assert_eq!(index.offset_utf16(LineColumn { line: 1, column: 2 }), Some(6));
+    assert_eq!(index.offset_utf16(LineColumn { line: 1, column: 3 }), None);
+    assert_eq!(index.offset_utf16(LineColumn { line: 1, column: 4 }), Some(10));
+    assert_eq!(index.offset_utf16(LineColumn { line: 1, column: 6 }), None);
+    assert_eq!(index.offset_utf16(LineColumn { line: 4, column: 0 }), None);
+
+    // Inside a character
+    assert_eq!(index.line_column_utf16(5), LineColumn { line: 1, column: 1 });
+    assert_eq!(index.line_column_utf16(8), LineColumn { line: 1, column: 2 });
+    assert_eq!(LineIndex::new("😀").line_column_utf16(1), LineColumn { line: 0, column: 0 });
+    assert_eq!(index.offset(LineColumn { line: 1, column: 2 }), None);
+    assert_eq!(index.offset(LineColumn { line: 1, column: 3 }), Some(6));
+    assert_eq!(LineIndex::new("bé").offset(LineColumn { line: 0, column: 2 }), None);
+}
+
+#[test]
+fn diagnostic_rendering() {
+    let source = "var a = 1;\nfunction f() => integer\n{}\ninteger function g() => string {}";
+    let diagnostics = validate_source(source);
+    assert_eq!(diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(), [codes::DUPLICATE_RETURN_TYPE]);
+    assert_eq!(diagnostics[0].render("main.leek", source, false), "\
+error[E0200]: The return type is already declared before the name
+ --> main.leek:4:22
+  |
+4 | integer function g() => string {}
+  |                      ^^^^^^^^^ second return type
+  | ------- first declared here
+  |
+  = help: Remove the second return type
+4 | integer function g()  {}
+");
+
+    let source = "var a =\n\t99999999999999999999;";
+    let diagnostic = &validate_source(source)[0];
+    assert_eq!(diagnostic.render("main.leek", source, false), "\
+error[E0105]: Integer `99999999999999999999` doesn't fit in 64 bits
+ --> main.leek:2:2
+  |
+2 | \t99999999999999999999;
+  | \t^^^^^^^^^^^^^^^^^^^^
+  |
+  = note: Integers range from -9223372036854775808 to 9223372036854775807
+");
+    assert!(diagnostic.render("main.leek", source, true).contains("\x1b[1;31merror[E0105]\x1b[0m"));
+}
+
+#[test]
+fn diagnostic_output() {
+    let source = "var s = 'é\\q';\ninteger function g() => string {}";
+    let diagnostics = validate_source(source);
+    let files = [SourceDiagnostics { path: "ai/main <1>.leek", source, diagnostics: &diagnostics }];
+    let json = to_json(&files);
+    assert!(json.starts_with("{\"version\":1,\"files\":[{\"path\":\"ai/main <1>.leek\",\"diagnostics\":[{\"severity\":\"error\",\"code\":\"E0100\",\
+        \"message\":\"Invalid escape sequence `\\\\q`\",\"span\":{\"start\":11,\"end\":13},\
+        \"range\":{\"start\":{\"line\":1,\"column\":11},\"end\":{\"line\":1,\"column\":13}},\"label\":null,\"secondary_labels\":[],"));
+    assert!(json.contains("\"secondary_labels\":[{\"span\":{\"start\":16,\"end\":23},\"range\":{\"start\":{\"line\":2,\"column\":1},\
+        \"end\":{\"line\":2,\"column\":8}},\"message\":\"first declared here\"}]"));
+
+    let sarif = to_sarif(&files);
+    assert!(sarif.contains("\"rules\":[{\"id\":\"E0100\"},{\"id\":\"E0200\"}]"));
+    assert!(sarif.contains("{\"ruleId\":\"E0200\",\"ruleIndex\":1,\"level\":\"error\",\
+        \"message\":{\"text\":\"The return type is already declared before the name\"},\"locations\":[{\"physicalLocation\":{\
+        \"artifactLocation\":{\"uri\":\"ai/main%20%3C1%3E.leek\"},\
+        \"region\":{\"startLine\":2,\"startColumn\":22,\"endLine\":2,\"endColumn\":31,\"byteOffset\":37,\"byteLength\":9}}}]"));
+    assert!(sarif.contains("\"insertedContent\":{\"text\":\"\\\\\\\\\"}"));
+
+    assert_eq!(to_checkstyle(&files), "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<checkstyle version=\"4.3\">
+  <file name=\"ai/main &lt;1&gt;.leek\">
+    <error line=\"1\" column=\"11\" severity=\"error\" message=\"Invalid escape sequence `\\q`\" source=\"leekwars_parser.E0100\"/>
+    <error line=\"2\" column=\"22\" severity=\"error\" message=\"The return type is already declared before the name\" source=\"leekwars_parser.E0200\"/>
+  </file>
+</checkstyle>
+");
+}
+
+#[test]
+fn incremental_reparse() {
+    let source = "class A { m(x) { this.x = [1..2]; } }\n\
+        function f(a) { switch (a) { default: { return 'é'; } } }\n\
+        while (true) { if (m) { f(x => { return x; }); } }";
+    let options = ParseOptions::default();
+    let previous = parse(source, options).unwrap();
+    let mut incremental = 0;
+    for start in (0..source.len()).filter(|start| source.is_char_boundary(*start)) {
+        let next = (start + 1..=source.len()).find(|end| source.is_char_boundary(*end)).unwrap_or(start);
+        for (end, replacement) in [(start, "a;"), (start, "}"), (start, "this"), (next, "")] {
+            let edit = TextEdit { start, end, replacement: String::from(replacement) };
+            let edited = edit.apply(source).unwrap();
+            // Otherwise the whole source is parsed again
+            if let Some(root) = reparse_block(previous.clone(), &edit, &edited, options) {
+                incremental += 1;
+                assert_eq!(Ok(root), parse(&edited, options), "{:?}", edit);
+            }
+        }
+    }
+    assert!(incremental > 100, "{}", incremental);
+
+    let edit = TextEdit { start: 0, end: 5, replacement: String::from("var") };
+    let edited = edit.apply(source).unwrap();
+    assert_eq!(reparse(previous, &edit, &edited, options), parse(&edited, options));
+}
+
+/// Sources where the grammar has to choose between several readings
+const AMBIGUOUS: [&str; 24] = [
+    "a ? b : c;",
+    "a ? b;",
+    "integer? a;",
+    "a < b > c;",
+    "a<b> c = d;",
+    "a | b;",
+    "a == b; a => b; a = b => c;",
+    "x -> x; x - -x; x->-x;",
+    "(a); (a, b) => a; (a) = 1;",
+    "a || b | c && d & e;",
+    "a >>= b; a >> -b; a > -b; a>>>=b;",
+    "a!; a! = b; a != b; a! == b; a !!= b;",
+    "a++ + b; a + ++b; a+++b;",
+    "a ** -b ** c * d;",
+    "[1..2]; [1...5]; [a..b]; ]1..-1]; [1. .. .2];",
+    "[1: 2]; [:]; [1]; [[1]: [2]];",
+    "1.5.foo; .5e3; 1.e5.x;",
+    "{}; {a: 1}; x => {}; x => {a: 1};",
+    "include('a'); include(a); include;",
+    "class A { m() { class.x; this.constructor; } } new A; new A();",
+    "var a var b = 1 global c global integer d",
+    "for (a in b) {} for (var a = 1; a; a++) {} for (a : b in c) {}",
+    "if (a) b else c; if (a) {} ; while (a) ; do ; while (a)",
+    " // comment\n\t/* block */ a /* inner */ . /**/ b // end",
+];
+
+#[test]
+fn hand_written_backend() {
+    let corpus = [
+        include_str!("corpus/fight.leek"),
+        include_str!("corpus/classes.leek"),
+        include_str!("corpus/expressions.leek"),
+    ];
+    let pest = ParseOptions::default();
+    let hand_written = ParseOptions { backend: Backend::HandWritten, ..pest };
+    for source in corpus.into_iter().chain(AMBIGUOUS) {
+        assert!(parser::parse(source).is_some(), "{:?} is left to pest", source);
+        let expected = parse(source, pest).unwrap_or_else(|errors| panic!("{:?}: {}", source, errors));
+        let root = parse(source, hand_written).unwrap();
+        assert_eq!(root, expected, "{:?}", source);
+        assert_eq!(root.print_ast(PrintProperties::default()), expected.print_ast(PrintProperties::default()));
+        assert_eq!(root.print_ast(PrintProperties::default()), source);
+    }
+
+    // Every character removed in turn, most of them being syntax errors reported by pest
+    for source in AMBIGUOUS {
+        for (start, char) in source.char_indices() {
+            let edited = [&source[..start], &source[start + char.len_utf8()..]].concat();
+            assert_eq!(parse(&edited, hand_written), parse(&edited, pest), "{:?}", edited);
+        }
+    }
+    for source in ["'unterminated", "/* unterminated", ".5.5;"] {
+        assert!(parser::parse(source).is_none());
+        assert_eq!(parse(source, hand_written), parse(source, pest));
+    }
+}
+
+#[test]
+fn tokens() {
+    let kinds = |source| tokenize(source).map(|token| (token.kind, token.text)).collect::<Vec<_>>();
+    use TokenKind::{Comment, Constant, Identifier, Keyword, Number, Operator, Symbol, Unknown, Whitespace};
+    assert_eq!(kinds("var a = b >>>= 1; // end"), [
+        (Keyword, "var"), (Whitespace, " "), (Identifier, "a"), (Whitespace, " "), (Operator, "="),
+        (Whitespace, " "), (Identifier, "b"), (Whitespace, " "), (Operator, ">>>="), (Whitespace, " "),
+        (Number, "1"), (Symbol, ";"), (Whitespace, " "), (Comment, "// end"),
+    ]);
+    assert_eq!(kinds("include('a')"), [(Identifier, "include"), (Symbol, "("), (TokenKind::String, "'a'"), (Symbol, ")")]);
+    assert_eq!(kinds("x=>!x.y"), [(Identifier, "x"), (Symbol, "=>"), (Operator, "!"), (Identifier, "x"), (Symbol, "."), (Identifier, "y")]);
+    assert_eq!(kinds("[1...5]"), [(Symbol, "["), (Number, "1"), (Symbol, ".."), (Number, ".5"), (Symbol, "]")]);
+    assert_eq!(kinds("12px+∞"), [(Number, "12px"), (Operator, "+"), (Constant, "∞")]);
+    assert_eq!(kinds("null and true"), [(Keyword, "null"), (Whitespace, " "), (Keyword, "and"), (Whitespace, " "), (Keyword, "true")]);
+    assert_eq!(kinds("# 'open"), [(Unknown, "#"), (Whitespace, " "), (Unknown, "'open")]);
+    assert_eq!(kinds("/* open"), [(Unknown, "/* open")]);
+
+    let token = tokenize("a\n  bc").last().unwrap();
+    assert_eq!((token.span, token.text), (Span { start: 4, end: 6 }, "bc"));
+
+    // Lossless, whatever the source
+    let corpus = [include_str!("corpus/fight.leek"), include_str!("corpus/classes.leek"), include_str!("corpus/expressions.leek")];
+    for source in corpus.into_iter().chain(AMBIGUOUS).chain(["é\r\n\u{c}\\'\\", "\"a\\", "@@#$`", ""]) {
+        let mut end = 0;
+        for token in tokenize(source) {
+            assert_eq!(token.span.start, end);
+            assert_eq!(&source[token.span.start..token.span.end], token.text);
+            assert!(!token.text.is_empty());
+            end = token.span.end;
+        }
+        assert_eq!(tokenize(source).map(|token| token.text).collect::<String>(), source);
+    }
+}
+
+#[test]
+fn arena_tree() {
+    use crate::arena::{self, ClassMember, Expression, ForHeader, ForInit, Identifier, MemberAccessor, TypedNode};
+    use lsv4::Rule;
+
+    let source = "var a = b + 1; // one\nif (a) ; ;\nfor (var i = 0; i < 10; i++) {}\nfor (k : integer v in [1, 2]) { x = 'é\\n' }\n\
+        class A extends B implements C { private static integer m(x) { return x!.y } ; ; }\n";
+    let ast = arena::parse(source, ParseOptions::default()).unwrap();
+    let statements: Vec<_> = ast.root().statements().collect();
+    assert_eq!(statements.len(), 5);
+
+    let arena::Statement::VariableDeclaration(declaration) = statements[0] else {
+        panic!("{:?}", statements[0]);
+    };
+    assert_eq!((declaration.name().name(), declaration.is_global(), declaration.declared_type()), ("a", false, None));
+    assert_eq!(declaration.text(), "var a = b + 1;");
+    let Some(Expression::Binary(sum)) = declaration.value() else {
+        panic!("{:?}", declaration.value());
+    };
+    assert_eq!(sum.operators().map(TypedNode::text).collect::<Vec<_>>(), ["+"]);
+    assert!(matches!(sum.operands().collect::<Vec<_>>()[..], [Expression::Identifier(_), Expression::Number(_)]));
+
+    // A `;` alone is an empty statement, the second one ends the `if`
+    let arena::Statement::If(if_statement) = statements[1] else {
+        panic!("{:?}", statements[1]);
+    };
+    assert!(matches!(if_statement.then_branch(), arena::Statement::Empty(_)));
+    assert_eq!(if_statement.else_branch(), None);
+
+    let arena::Statement::For(for_statement) = statements[2] else {
+        panic!("{:?}", statements[2]);
+    };
+    let ForHeader::Counter(counter) = for_statement.header() else {
+        panic!("{:?}", for_statement.header());
+    };
+    assert!(matches!(counter.init(), Some(ForInit::Declaration(_))));
+    assert_eq!(counter.condition().map(TypedNode::text), Some("i < 10"));
+    assert_eq!(counter.update().map(TypedNode::text), Some("i++"));
+
+    let arena::Statement::For(for_statement) = statements[3] else {
+        panic!("{:?}", statements[3]);
+    };
+    let ForHeader::In(for_in) = for_statement.header() else {
+        panic!("{:?}", for_statement.header());
+    };
+    assert_eq!((for_in.key().map(Identifier::name), for_in.key_type()), (Some("k"), None));
+    assert_eq!((for_in.value().name(), for_in.value_type().map(TypedNode::text)), ("v", Some("integer")));
+    let Expression::Array(array) = for_in.iterable() else {
+        panic!("{:?}", for_in.iterable());
+    };
+    assert_eq!(array.elements().count(), 2);
+    let arena::Statement::Block(block) = for_statement.body() else {
+        panic!("{:?}", for_statement.body());
+    };
+    let Some(arena::Statement::Expression(statement)) = block.statements().next() else {
+        panic!("{:?}", block);
+    };
+    let Expression::Assignment(assignment) = statement.expression() else {
+        panic!("{:?}", statement.expression());
+    };
+    let Expression::String(string) = assignment.value() else {
+        panic!("{:?}", assignment.value());
+    };
+    assert_eq!(string.value(), "é\n");
+
+    let arena::Statement::ClassDeclaration(class) = statements[4] else {
+        panic!("{:?}", statements[4]);
+    };
+    assert_eq!((class.name().name(), class.parent().map(Identifier::name)), ("A", Some("B")));
+    assert_eq!(class.interfaces().map(Identifier::name).collect::<Vec<_>>(), ["C"]);
+    let members: Vec<_> = class.members().collect();
+    let [ClassMember::Method(method), ClassMember::Empty(_)] = members[..] else {
+        panic!("{:?}", members);
+    };
+    assert_eq!(method.visibility().map(TypedNode::text), Some("private"));
+    assert!(method.is_static());
+    assert_eq!(method.return_type().map(TypedNode::text), Some("integer"));
+    assert_eq!(method.parameters().map(|parameter| parameter.name().name()).collect::<Vec<_>>(), ["x"]);
+    let Some(arena::Statement::Return(return_statement)) = method.body().statements().next() else {
+        panic!("{:?}", method.body());
+    };
+    let Some(Expression::Member(member)) = return_statement.value() else {
+        panic!("{:?}", return_statement.value());
+    };
+    assert!(matches!(member.object(), Expression::Identifier(_)));
+    let accessors: Vec<_> = member.accessors().collect();
+    let [MemberAccessor::Nullable(_), MemberAccessor::Property(property)] = accessors[..] else {
+        panic!("{:?}", accessors);
+    };
+    assert_eq!(property.name(), "y");
+
+    // The same names have the same symbol, the keywords the same one in every tree
+    let x: Vec<_> = ast.root().node().descendants().filter_map(Identifier::cast).filter(|name| name.name() == "x").collect();
+    assert_eq!(x.len(), 3);
+    assert!(x.iter().all(|name| name.symbol() == x[0].symbol()));
+    let other = arena::parse("var b;", ParseOptions::default()).unwrap();
+    let var = |ast: &arena::Ast| ast.root().node().descendants().find(|node| node.rule() == Rule::Var).and_then(|node| node.symbol());
+    assert_eq!(var(&ast), var(&other));
+    assert_eq!(var(&ast), ast.interner().get("var"));
+
+    // What another tree has past the end of this one isn't found in it
+    let y = ast.interner().get("y").unwrap();
+    assert_eq!((ast.interner().name(y), other.interner().name(y)), (Some("y"), None));
+    let last = ast.root().node().descendants().last().unwrap().id();
+    assert_eq!(ast.get(last).map(|node| node.rule()), Some(Rule::EOI));
+    assert!(other.get(last).is_none());
+
+    // Same statements and spans as the syntax tree, every name being interned
+    let corpus = [include_str!("corpus/fight.leek"), include_str!("corpus/classes.leek"), include_str!("corpus/expressions.leek")];
+    for source in corpus.into_iter().chain(AMBIGUOUS).chain([source]) {
+        let mut root = parse(source, ParseOptions::default()).unwrap();
+        let ast = arena::parse(source, ParseOptions::default()).unwrap();
+        let spans: Vec<_> = ast.root().statements().map(TypedNode::span).collect();
+        assert_eq!(spans, root.statements.iter().map(|statement| statement.span()).collect::<Vec<_>>(), "{:?}", source);
+
+        // Every node has the span of a node of the syntax tree
+        let mut syntax_spans = HashSet::new();
+        root.visit_nodes(&mut |meta: &mut AstNodeMeta, _: &mut dyn Any| {
+            syntax_spans.insert(meta.span);
+            true
+        });
+        for node in ast.root().node().descendants().filter(|node| node.rule() != Rule::COMMENT) {
+            assert!(syntax_spans.contains(&node.span()), "{:?} in {:?}", node, source);
+        }
+
+        // Printed back from its leaves and the whitespace between them
+        let mut printed = String::new();
+        for leaf in ast.root().node().descendants().filter(|node| node.children().next().is_none()) {
+            let whitespace = &source[printed.len()..leaf.span().start];
+            assert!(whitespace.trim().is_empty(), "{:?} before {:?}", whitespace, leaf);
+            printed.push_str(whitespace);
+            printed.push_str(leaf.text());
+        }
+        assert!(source[printed.len()..].trim().is_empty());
+        printed.push_str(&source[printed.len()..]);
+        assert_eq!(printed, source);
+
+        for node in ast.root().node().descendants() {
+            if let Some(symbol) = node.symbol() {
+                assert_eq!(ast.resolve(symbol), node.text());
+            }
+        }
+    }
+
+    // Only the pairs of `lsv4_root` make a tree
+    let pairs = lsv4::Lsv4Parser::parse(Rule::Expression, "a + 1").unwrap();
+    let errors = arena::Ast::from_pairs("a + 1", pairs).unwrap_err();
+    assert_eq!(errors.diagnostics.iter().map(|d| (d.code, d.start, d.end)).collect::<Vec<_>>(), [(codes::CONVERSION_FAILURE, 0, 5)]);
+    let deep = format!("{}{}", "{".repeat(150), "}".repeat(150));
+    let pairs = lsv4::Lsv4Parser::parse(Rule::lsv4_root, &deep).unwrap();
+    let errors = arena::Ast::from_pairs(&deep, pairs).unwrap_err();
+    assert_eq!(errors.diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(), [codes::TOO_DEEP]);
+}
@@ -0,0 +1,117 @@
+use crate::ast::Span;
+use crate::lexer::{self, leading_dot_real, token_at, RESERVED, VERSIONED_KEYWORDS};
+
+/// What a token of [`tokenize`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A run of spaces, tabs and line terminators
+    Whitespace,
+    /// `// ...` up to the end of the line, or `/* ... */`
+    Comment,
+    /// A keyword of LeekScript 4, `true`, `false` and `null` included, even where an older version
+    /// takes it as a name like `new`
+    Keyword,
+    /// Any other word, as `include` or `constructor` which aren't reserved
+    Identifier,
+    /// An integer or a real, in any base, malformed ones like `12px` included
+    Number,
+    /// A single or double quoted string, with its quotes
+    String,
+    /// `∞` or `π`
+    Constant,
+    /// Brackets, `;`, `,`, `.`, `..`, `:`, `?` and the arrows `=>` and `->`
+    Symbol,
+    /// Arithmetic, comparison, logical, bitwise, assignment and update operators, `@` and `!`
+    Operator,
+    /// What can't start a token: an unterminated string or comment, or a character like `#`
+    Unknown,
+}
+
+/// A token of a source, with its kind, where it is and its text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub span: Span,
+    pub text: &'a str,
+}
+
+/// The symbols and operators made of several characters, tried before the single ones
+const SYMBOLS: [&str; 3] = ["..", "=>", "->"];
+const OPERATORS: [&str; 28] = [
+    ">>>=", "===", "!==", "**=", "<<=", ">>=", ">>>", "==", "!=", "<=", ">=", "&&", "||", "^^", "++",
+    "--", "**", "<<", ">>", "+=", "-=", "*=", "/=", "\\=", "%=", "&=", "|=", "^=",
+];
+const SINGLE_OPERATORS: &str = "+-*/\\%=<>!&|^~@";
+const SINGLE_SYMBOLS: &str = "{}[]();,.:?";
+
+/// Splits a source into tokens, whitespace and comments included.
+///
+/// The texts of the tokens put end to end give back the source. The tokens don't depend on what
+/// is around them: operators are the longest ones the source continues with, so the `>>` closing
+/// `Array<Array<integer>>` is a single operator, and `<` is always an operator too.
+pub fn tokenize(source: &str) -> Tokens<'_> {
+    Tokens { source, position: 0 }
+}
+
+/// The iterator of [`tokenize`]
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.position;
+        if start >= self.source.len() {
+            return None;
+        }
+        let token = token_at(self.source, start);
+        let (kind, end) = match token.kind {
+            lexer::TokenKind::Whitespace => (TokenKind::Whitespace, token.end),
+            lexer::TokenKind::LineComment | lexer::TokenKind::BlockComment => (TokenKind::Comment, token.end),
+            lexer::TokenKind::Word if is_keyword(&self.source[start..token.end]) => (TokenKind::Keyword, token.end),
+            lexer::TokenKind::Word => (TokenKind::Identifier, token.end),
+            lexer::TokenKind::Number(_) => (TokenKind::Number, token.end),
+            lexer::TokenKind::String => (TokenKind::String, token.end),
+            lexer::TokenKind::Other if matches!(&self.source[start..token.end], "∞" | "π") => (TokenKind::Constant, token.end),
+            lexer::TokenKind::Other | lexer::TokenKind::Unterminated => (TokenKind::Unknown, token.end),
+            lexer::TokenKind::Punct => self.punctuation(start),
+        };
+        self.position = end;
+        Some(Token { kind, span: Span { start, end }, text: &self.source[start..end] })
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    RESERVED.contains(&word) || VERSIONED_KEYWORDS.contains(&word)
+}
+
+impl Tokens<'_> {
+    fn punctuation(&self, start: usize) -> (TokenKind, usize) {
+        let rest = &self.source[start..];
+        if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            return (TokenKind::Symbol, start + symbol.len());
+        }
+        if let Some(operator) = OPERATORS.iter().find(|operator| rest.starts_with(**operator)) {
+            return (TokenKind::Operator, start + operator.len());
+        }
+        // As the grammar reads `[1...5]`
+        if rest.starts_with('.') {
+            if let Some(end) = leading_dot_real(self.source, start) {
+                return (TokenKind::Number, end);
+            }
+        }
+        let char = rest.as_bytes()[0] as char;
+        let kind = if SINGLE_SYMBOLS.contains(char) {
+            TokenKind::Symbol
+        } else if SINGLE_OPERATORS.contains(char) {
+            TokenKind::Operator
+        } else {
+            TokenKind::Unknown
+        };
+        (kind, start + 1)
+    }
+}
@@ -1,5 +1,8 @@
-use from_pest::{ConversionError, Void};
+#[cfg(test)]
+use std::cell::Cell;
+use from_pest::ConversionError;
 use pest::iterators::{Pair, Pairs};
+use crate::ast::conversion::ConversionFailure;
 use crate::lsv4::Rule;
 
 #[derive(Clone, Copy)]
@@ -25,13 +28,26 @@ pub trait PrintAst {
     fn print_ast(&self, print_properties: PrintProperties) -> String;
 }
 
+#[cfg(test)]
+thread_local! {
+    /// The pairs looked at around the nodes being converted, which the tests check grow linearly
+    pub(crate) static VISITED_PAIRS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts a pair looked at while converting, in the tests
+pub(crate) fn visit_pair() {
+    #[cfg(test)]
+    VISITED_PAIRS.with(|visited| visited.set(visited.get() + 1));
+}
+
 pub fn find_next_non_comment_or_whitespace<'a>(
     pairs: &mut Pairs<'a, Rule>,
-) -> Result<Option<Pair<'a, Rule>>, ConversionError<Void>> {
+) -> Result<Option<Pair<'a, Rule>>, ConversionError<ConversionFailure>> {
     let mut p = pairs.clone(); // To avoid eating the items
     loop {
         let pair = p.next();
         if let Some(pair) = pair {
+            visit_pair();
             match pair.as_rule() {
                 Rule::COMMENT | Rule::WHITESPACE => continue,
                 _ => return Ok(Some(pair)),
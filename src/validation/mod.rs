@@ -0,0 +1,202 @@
+use std::ops::RangeInclusive;
+use pest::iterators::{Pair, Pairs};
+use crate::ast::number::{evaluate_number, NumberErrorKind};
+use crate::ast::string::decode_string;
+use crate::diagnostic::{codes, Diagnostic};
+use crate::lsv4::Rule;
+use crate::version::LanguageVersion;
+use crate::grow_stack;
+
+/// Checks the parsed source for the problems the grammar lets through, including the constructs
+/// that don't exist in `version`
+pub fn validate(pairs: Pairs<Rule>, version: LanguageVersion) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    validate_pairs(pairs, version, false, &mut diagnostics);
+    diagnostics
+}
+
+/// Like [`validate`], for pairs taken out of a source, inside a class body if `in_class`
+pub(crate) fn validate_nested(pairs: Pairs<Rule>, version: LanguageVersion, in_class: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    validate_pairs(pairs, version, in_class, &mut diagnostics);
+    diagnostics
+}
+
+/// `in_class` tells whether the pairs are inside a class body
+fn validate_pairs(pairs: Pairs<Rule>, version: LanguageVersion, in_class: bool, diagnostics: &mut Vec<Diagnostic>) {
+    for pair in pairs {
+        // Before LeekScript 2, `this` and `super` outside of a class are variables
+        let is_name = version < LanguageVersion::V2 && !in_class && matches!(pair.as_rule(), Rule::This | Rule::Super);
+        if let Some((construct, versions)) = construct_versions(&pair).filter(|_| !is_name) {
+            if !versions.contains(&version) {
+                diagnostics.push(version_diagnostic(&pair, construct, version, versions));
+                // The types nested in a type would only repeat the diagnostic
+                if pair.as_rule() == Rule::Type {
+                    continue;
+                }
+            }
+        }
+        match pair.as_rule() {
+            Rule::String => validate_string(&pair, diagnostics),
+            Rule::Number => validate_number(&pair, diagnostics),
+            Rule::FunctionDeclaration | Rule::MethodDeclaration | Rule::MethodSignature => {
+                validate_return_type(&pair, diagnostics)
+            }
+            Rule::Identifier => validate_name(&pair, version, diagnostics),
+            Rule::PrimaryExpression if !in_class => validate_class_reference(&pair, version, diagnostics),
+            _ => {}
+        }
+        let in_class = in_class || pair.as_rule() == Rule::ClassBody;
+        grow_stack(|| validate_pairs(pair.into_inner(), version, in_class, diagnostics));
+    }
+}
+
+fn validate_string(pair: &Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) {
+    let raw = pair.as_str();
+    let offset = pair.as_span().start();
+    let (_, invalid_escapes) = decode_string(raw);
+    for invalid_escape in invalid_escapes {
+        let start = offset + invalid_escape.start;
+        let end = offset + invalid_escape.end;
+        let escape = &raw[invalid_escape.start..invalid_escape.end];
+        diagnostics.push(
+            Diagnostic::error(codes::INVALID_ESCAPE, format!("Invalid escape sequence `{}`", escape), start, end)
+                .with_note("The escape sequences are `\\n`, `\\t`, `\\r`, `\\b`, `\\f`, `\\\\`, `\\'`, `\\\"` and `\\uXXXX`")
+                .with_fix("Escape the backslash", start, start + 1, "\\\\"),
+        );
+    }
+}
+
+fn validate_number(pair: &Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) {
+    let raw = pair.as_str();
+    let offset = pair.as_span().start();
+    let (_, errors) = evaluate_number(raw);
+    for error in errors {
+        let text = &raw[error.start..error.end];
+        let (code, message) = match error.kind {
+            NumberErrorKind::MisplacedSeparator => (codes::MISPLACED_SEPARATOR, String::from("Digit separators `_` must be between two digits")),
+            NumberErrorKind::MissingDigits => (codes::MISSING_DIGITS, format!("Missing digits after `{}`", &raw[..2])),
+            NumberErrorKind::InvalidDigit => (codes::INVALID_DIGIT, format!("Invalid digit `{}` in number `{}`", text, raw)),
+            NumberErrorKind::Malformed => (codes::MALFORMED_NUMBER, format!("Malformed number `{}`", raw)),
+            NumberErrorKind::IntegerOverflow => (codes::INTEGER_OVERFLOW, format!("Integer `{}` doesn't fit in 64 bits", raw)),
+            NumberErrorKind::RealOverflow => (codes::REAL_OVERFLOW, format!("Real `{}` is too large", raw)),
+        };
+        let start = offset + error.start;
+        let end = offset + error.end;
+        let diagnostic = Diagnostic::error(code, message, start, end);
+        diagnostics.push(match error.kind {
+            NumberErrorKind::MisplacedSeparator => diagnostic.with_fix("Remove the separators", start, end, ""),
+            NumberErrorKind::IntegerOverflow => diagnostic.with_note("Integers range from -9223372036854775808 to 9223372036854775807"),
+            _ => diagnostic,
+        });
+    }
+}
+
+/// The return type can be written before the name or after the parameters, but not both
+fn validate_return_type(pair: &Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut inner = pair.clone().into_inner();
+    let leading_type = inner.find(|inner| inner.as_rule() == Rule::Type);
+    let return_type = inner.find(|inner| inner.as_rule() == Rule::ReturnType);
+    if let (Some(leading_type), Some(return_type)) = (leading_type, return_type) {
+        // The spans of the types also cover the whitespace that follows them
+        let (start, end) = trimmed_span(&return_type);
+        let (type_start, type_end) = trimmed_span(&leading_type);
+        diagnostics.push(
+            Diagnostic::error(codes::DUPLICATE_RETURN_TYPE, "The return type is already declared before the name", start, end)
+                .with_label("second return type")
+                .with_secondary_label(type_start, type_end, "first declared here")
+                .with_fix("Remove the second return type", start, end, ""),
+        );
+    }
+}
+
+/// The keywords added after LeekScript 1 are names in the versions before them only
+fn validate_name(pair: &Pair<Rule>, version: LanguageVersion, diagnostics: &mut Vec<Diagnostic>) {
+    use LanguageVersion::*;
+    let name = pair.as_str();
+    let since = match name {
+        "this" | "super" | "new" => V2,
+        "enum" | "switch" | "case" | "default" | "interface" | "implements" => V4,
+        _ => return,
+    };
+    if version >= since {
+        diagnostics.push(
+            Diagnostic::error(
+                codes::RESERVED_NAME,
+                format!("`{}` is a keyword in {} and can't be used as a name", name, version),
+                pair.as_span().start(),
+                pair.as_span().end(),
+            ).with_note(format!("It is only a name before {}", since)),
+        );
+    }
+}
+
+/// `this`, `super` and `class` only have a meaning in the methods of a class
+fn validate_class_reference(pair: &Pair<Rule>, version: LanguageVersion, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(inner) = pair.clone().into_inner().next() else {
+        return;
+    };
+    // Before LeekScript 2 they are variables, `class` excepted
+    if version < LanguageVersion::V2 && matches!(inner.as_rule(), Rule::This | Rule::Super) {
+        return;
+    }
+    if matches!(inner.as_rule(), Rule::This | Rule::Super | Rule::Class) {
+        diagnostics.push(Diagnostic::error(
+            codes::OUTSIDE_OF_CLASS,
+            format!("`{}` can only be used inside a class", inner.as_str()),
+            inner.as_span().start(),
+            inner.as_span().end(),
+        ));
+    }
+}
+
+/// The constructs that only exist in some versions of LeekScript
+fn construct_versions(pair: &Pair<Rule>) -> Option<(&'static str, RangeInclusive<LanguageVersion>)> {
+    use LanguageVersion::*;
+    let first_inner = || pair.clone().into_inner().next().map(|inner| inner.as_rule());
+    Some(match pair.as_rule() {
+        Rule::Reference => ("The `@` reference", V1..=V3),
+        Rule::EqualityOperator if matches!(pair.as_str(), "===" | "!==") => ("Strict equality", V1..=V3),
+        Rule::ClassDeclaration => ("The class declaration", V2..=V4),
+        Rule::This => ("`this`", V2..=V4),
+        Rule::Super => ("`super`", V2..=V4),
+        Rule::NewExpression => ("`new`", V2..=V4),
+        Rule::PrimaryExpression if first_inner() == Some(Rule::Class) => ("`class`", V2..=V4),
+        Rule::Object => ("The object literal", V2..=V4),
+        Rule::Type => ("The type annotation", V4..=V4),
+        Rule::Interval => ("The interval", V4..=V4),
+        Rule::Set => ("The set literal", V4..=V4),
+        Rule::SwitchStatement => ("The switch statement", V4..=V4),
+        Rule::EnumDeclaration => ("The enum declaration", V4..=V4),
+        Rule::InterfaceDeclaration => ("The interface declaration", V4..=V4),
+        _ => return None,
+    })
+}
+
+fn version_diagnostic(pair: &Pair<Rule>, construct: &str, version: LanguageVersion, versions: RangeInclusive<LanguageVersion>) -> Diagnostic {
+    let available = if versions.start() == versions.end() {
+        format!("{}", versions.start())
+    } else {
+        format!("{} to {}", versions.start(), versions.end().number())
+    };
+    // Point at the keyword of a declaration rather than at its whole body
+    let target = match pair.as_rule() {
+        Rule::ClassDeclaration | Rule::SwitchStatement | Rule::EnumDeclaration | Rule::InterfaceDeclaration => {
+            pair.clone().into_inner().next().unwrap_or(pair.clone())
+        }
+        _ => pair.clone(),
+    };
+    let (start, end) = trimmed_span(&target);
+    Diagnostic::error(
+        codes::UNAVAILABLE_IN_VERSION,
+        format!("{} is not available in {}, only in {}", construct, version, available),
+        start,
+        end,
+    ).with_note(format!("The code is checked against {}", version))
+}
+
+/// The span of a pair without the whitespace it ends with
+fn trimmed_span(pair: &Pair<Rule>) -> (usize, usize) {
+    let start = pair.as_span().start();
+    (start, start + pair.as_str().trim_end().len())
+}
@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// The versions of LeekScript. They share one grammar, the constructs a version doesn't have
+/// are reported by the validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LanguageVersion {
+    V1,
+    V2,
+    V3,
+    #[default]
+    V4,
+}
+
+impl LanguageVersion {
+    pub fn from_number(number: u8) -> Option<LanguageVersion> {
+        match number {
+            1 => Some(LanguageVersion::V1),
+            2 => Some(LanguageVersion::V2),
+            3 => Some(LanguageVersion::V3),
+            4 => Some(LanguageVersion::V4),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            LanguageVersion::V1 => 1,
+            LanguageVersion::V2 => 2,
+            LanguageVersion::V3 => 3,
+            LanguageVersion::V4 => 4,
+        }
+    }
+}
+
+impl fmt::Display for LanguageVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LeekScript {}", self.number())
+    }
+}